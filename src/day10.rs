@@ -0,0 +1,232 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    grid::{Dir, Grid, Pos},
+    parse::{Cursor, ParseError},
+    polygon::interior_points,
+};
+
+#[aoc_generator(day10)]
+fn parse_input(input: &str) -> Result<Maze, ParseError> {
+    let input = Cursor::new(10, input);
+
+    let mut grid = Grid::parse_tiles(input, "|-LJ7F.S", "pipe tile")?;
+    let start = grid
+        .position(|&tile| tile == b'S')
+        .ok_or_else(|| input.error("`S` tile"))?;
+
+    // the start tile connects to the neighbouring pipes that connect back to it
+    let dirs = Dir::ALL
+        .into_iter()
+        .filter(|&dir| {
+            grid.step(start, dir)
+                .is_some_and(|pos| connections(grid[pos]).contains(&dir.reverse()))
+        })
+        .collect::<Vec<_>>();
+    let &[a, b] = &dirs[..] else {
+        return Err(error_at(input, start, "`S` connected to exactly two pipes"));
+    };
+    grid[start] = tile(a, b);
+
+    let mut path = vec![start];
+    let (mut pos, mut dir) = (start, a);
+    loop {
+        pos = grid
+            .step(pos, dir)
+            .ok_or_else(|| error_at(input, pos, "pipe inside the grid"))?;
+        if pos == start {
+            break;
+        }
+
+        dir = match (connections(grid[pos]), dir.reverse()) {
+            (&[a, b], from) if from == a => b,
+            (&[a, b], from) if from == b => a,
+            _ => return Err(error_at(input, pos, "pipe connected to the loop")),
+        };
+        path.push(pos);
+    }
+
+    Ok(Maze { grid, path })
+}
+
+/// Returns an error located at `pos` in the grid.
+fn error_at(input: Cursor, pos: Pos, expected: &'static str) -> ParseError {
+    let mut line = input.lines().nth(pos.row).unwrap();
+    match line.field(pos.col) {
+        Ok(_) => line.error(expected),
+        Err(e) => e,
+    }
+}
+
+#[aoc(day10, part1)]
+fn part1(maze: &Maze) -> usize {
+    maze.path.len() / 2
+}
+
+#[aoc(day10, part2, scanline)]
+fn part2_scanline(maze: &Maze) -> usize {
+    let mut on_path = Grid::new(maze.grid.width(), maze.grid.height(), false);
+    for &pos in &maze.path {
+        on_path[pos] = true;
+    }
+
+    let mut count = 0;
+    for row in 0..maze.grid.height() {
+        // a tile is inside the loop iff a ray from it to the left edge
+        // crosses the loop an odd number of times. Only counting the pipes
+        // that connect upwards handles rays running along a horizontal pipe.
+        let mut inside = false;
+        for col in 0..maze.grid.width() {
+            let pos = Pos::new(row, col);
+            if on_path[pos] {
+                inside ^= connections(maze.grid[pos]).contains(&Dir::Up);
+            } else if inside {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+#[aoc(day10, part2, shoelace)]
+fn part2_shoelace(maze: &Maze) -> usize {
+    // the enclosed tiles are the lattice points inside the loop through the
+    // centres of its tiles
+    let vertices = maze
+        .path
+        .iter()
+        .map(|pos| (pos.row as i64, pos.col as i64))
+        .collect::<Vec<_>>();
+
+    interior_points(&vertices) as usize
+}
+
+/// Returns the directions in which a tile connects to its neighbours.
+fn connections(tile: u8) -> &'static [Dir] {
+    match tile {
+        b'|' => &[Dir::Up, Dir::Down],
+        b'-' => &[Dir::Left, Dir::Right],
+        b'L' => &[Dir::Up, Dir::Right],
+        b'J' => &[Dir::Up, Dir::Left],
+        b'7' => &[Dir::Down, Dir::Left],
+        b'F' => &[Dir::Down, Dir::Right],
+        // ground connects to nothing, and so does the start tile until its
+        // pipe is known
+        _ => &[],
+    }
+}
+
+/// Returns the pipe tile connecting the directions `a` and `b`.
+fn tile(a: Dir, b: Dir) -> u8 {
+    b"|-LJ7F"
+        .iter()
+        .copied()
+        .find(|&tile| connections(tile).contains(&a) && connections(tile).contains(&b))
+        .unwrap()
+}
+
+#[derive(Clone, Debug)]
+struct Maze {
+    /// The pipe grid, with the start tile replaced by its pipe.
+    grid: Grid<u8>,
+    /// The tiles on the loop, in order, starting with the start tile.
+    path: Vec<Pos>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    const EXAMPLE_2: &str = "\
+7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ";
+
+    const EXAMPLE_3: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const EXAMPLE_4: &str = "\
+..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    const EXAMPLE_5: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const EXAMPLE_6: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE_1).unwrap()), 4);
+        assert_eq!(part1(&parse_input(EXAMPLE_2).unwrap()), 8);
+    }
+
+    #[test]
+    fn part2_example() {
+        for (example, expected) in [
+            (EXAMPLE_3, 4),
+            (EXAMPLE_4, 4),
+            (EXAMPLE_5, 8),
+            (EXAMPLE_6, 10),
+        ] {
+            let maze = parse_input(example).unwrap();
+            assert_eq!(part2_scanline(&maze), expected);
+            assert_eq!(part2_shoelace(&maze), expected);
+        }
+    }
+
+    #[test]
+    fn start_tile() {
+        assert_eq!(parse_input(EXAMPLE_1).unwrap().grid[Pos::new(1, 1)], b'F');
+        assert_eq!(parse_input(EXAMPLE_6).unwrap().grid[Pos::new(0, 4)], b'7');
+    }
+
+    #[test]
+    fn broken_loop() {
+        let err = parse_input("S-7\n|.|\nL-.").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+    }
+}
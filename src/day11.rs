@@ -0,0 +1,121 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    grid::{Grid, Pos},
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day11)]
+fn parse_input(input: &str) -> Result<Image, ParseError> {
+    let input = Cursor::new(11, input);
+
+    let grid = Grid::parse_tiles(input, ".#", "`.` or `#`")?;
+    let galaxies = grid
+        .iter()
+        .filter(|&(_, &cell)| cell == b'#')
+        .map(|(pos, _)| pos)
+        .collect();
+    let empty_rows = (0..grid.height())
+        .filter(|&row| !grid.row(row).contains(&b'#'))
+        .collect();
+    let empty_cols = (0..grid.width())
+        .filter(|&col| !grid.column(col).any(|&cell| cell == b'#'))
+        .collect();
+
+    Ok(Image {
+        galaxies,
+        empty_rows,
+        empty_cols,
+    })
+}
+
+#[aoc(day11, part1)]
+fn part1(image: &Image) -> u64 {
+    image.sum_distances(2)
+}
+
+#[aoc(day11, part2)]
+fn part2(image: &Image) -> u64 {
+    image.sum_distances(1_000_000)
+}
+
+#[derive(Clone, Debug)]
+struct Image {
+    galaxies: Vec<Pos>,
+    /// Indices of the rows without galaxies, in ascending order.
+    empty_rows: Vec<usize>,
+    /// Indices of the columns without galaxies, in ascending order.
+    empty_cols: Vec<usize>,
+}
+
+impl Image {
+    /// Returns the sum of the distances between all pairs of galaxies, after
+    /// every empty row and column has been replaced by `factor` of them.
+    fn sum_distances(&self, factor: u64) -> u64 {
+        // Manhattan distances are the sum of the distances along each axis,
+        // which can be summed separately
+        let rows = self
+            .galaxies
+            .iter()
+            .map(|galaxy| expand(galaxy.row, &self.empty_rows, factor));
+        let cols = self
+            .galaxies
+            .iter()
+            .map(|galaxy| expand(galaxy.col, &self.empty_cols, factor));
+
+        sum_distances_1d(rows) + sum_distances_1d(cols)
+    }
+}
+
+/// Returns the row (or column) index after every empty row (or column) has
+/// been replaced by `factor` of them.
+fn expand(coord: usize, empty: &[usize], factor: u64) -> u64 {
+    let empty_before = empty.partition_point(|&i| i < coord) as u64;
+    coord as u64 + empty_before * (factor - 1)
+}
+
+/// Returns the sum of the distances between all pairs of points on a line.
+fn sum_distances_1d(coords: impl Iterator<Item = u64>) -> u64 {
+    let mut coords = coords.collect::<Vec<_>>();
+    coords.sort_unstable();
+
+    // the point at index i is to the right of the i points before it, so it
+    // contributes i * x minus the sum of their coordinates
+    let mut prefix_sum = 0;
+    let mut total = 0;
+    for (i, &x) in coords.iter().enumerate() {
+        total += i as u64 * x - prefix_sum;
+        prefix_sum += x;
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 374);
+    }
+
+    #[test]
+    fn expansion_factors() {
+        let image = parse_input(EXAMPLE).unwrap();
+        assert_eq!(image.sum_distances(10), 1030);
+        assert_eq!(image.sum_distances(100), 8410);
+    }
+}
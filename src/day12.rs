@@ -0,0 +1,194 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+use rayon::prelude::*;
+
+use crate::utils::parse::{Cursor, ParseError};
+
+#[aoc_generator(day12)]
+fn parse_input(input: &str) -> Result<Vec<Record>, ParseError> {
+    Cursor::new(12, input)
+        .lines()
+        .map(|line| {
+            let (mut springs, groups) = line.split_once(" ")?;
+            let springs_str = springs.as_str();
+            springs.take_while(|ch| matches!(ch, '.' | '#' | '?'));
+            if !springs.is_empty() {
+                return Err(springs.error("`.`, `#` or `?`"));
+            }
+
+            let groups = groups
+                .delimited(",")
+                .map(|mut group| {
+                    let len = group.int()?;
+                    if len == 0 {
+                        return Err(group.error("positive group size"));
+                    }
+                    group.end()?;
+                    Ok(len)
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(Record {
+                springs: springs_str.as_bytes().to_vec(),
+                groups,
+            })
+        })
+        .collect()
+}
+
+#[aoc(day12, part1)]
+fn part1(records: &[Record]) -> u64 {
+    records.iter().map(Record::count_arrangements).sum()
+}
+
+#[aoc(day12, part2)]
+fn part2(records: &[Record]) -> u64 {
+    records
+        .iter()
+        .map(|record| record.unfold().count_arrangements())
+        .sum()
+}
+
+#[aoc(day12, part2, parallel)]
+fn part2_parallel(records: &[Record]) -> u64 {
+    records
+        .par_iter()
+        .map(|record| record.unfold().count_arrangements())
+        .sum()
+}
+
+/// A row of springs, and the sizes of the contiguous groups of damaged
+/// springs in it.
+#[derive(Clone, Debug)]
+struct Record {
+    /// Each spring is `.` if operational, `#` if damaged or `?` if unknown.
+    springs: Vec<u8>,
+    groups: Vec<usize>,
+}
+
+impl Record {
+    /// Returns the record with its springs and groups repeated five times,
+    /// with unknown springs between the copies of the springs.
+    fn unfold(&self) -> Self {
+        let mut springs = self.springs.clone();
+        for _ in 1..5 {
+            springs.push(b'?');
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Self {
+            springs,
+            groups: self.groups.repeat(5),
+        }
+    }
+
+    /// Returns the number of ways to replace the unknown springs so that the
+    /// damaged springs form exactly the given groups.
+    fn count_arrangements(&self) -> u64 {
+        let springs = &self.springs;
+        let (n, m) = (springs.len(), self.groups.len());
+
+        // run[i] is the number of consecutive springs starting at i that
+        // could be damaged
+        let mut run = vec![0; n + 1];
+        for i in (0..n).rev() {
+            if springs[i] != b'.' {
+                run[i] = run[i + 1] + 1;
+            }
+        }
+
+        // ways[i * (m + 1) + j] is the number of arrangements of springs[i..]
+        // into groups[j..]
+        let mut ways = vec![0u64; (n + 1) * (m + 1)];
+        ways[n * (m + 1) + m] = 1;
+
+        for i in (0..n).rev() {
+            for j in 0..=m {
+                let mut count = 0;
+
+                // the spring is operational
+                if springs[i] != b'#' {
+                    count += ways[(i + 1) * (m + 1) + j];
+                }
+
+                // the spring starts the next group, which must be followed by
+                // an operational spring or the end of the row
+                if let Some(&len) = self.groups.get(j) {
+                    if run[i] >= len && springs.get(i + len) != Some(&b'#') {
+                        let next = (i + len + 1).min(n);
+                        count += ways[next * (m + 1) + j + 1];
+                    }
+                }
+
+                ways[i * (m + 1) + j] = count;
+            }
+        }
+
+        ways[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 21);
+    }
+
+    #[test]
+    fn part2_example() {
+        let records = parse_input(EXAMPLE).unwrap();
+        assert_eq!(part2(&records), 525152);
+        assert_eq!(part2_parallel(&records), 525152);
+    }
+
+    /// Counts arrangements by trying every way of replacing unknown springs.
+    fn count_arrangements_brute_force(record: &Record) -> u64 {
+        let unknown = record
+            .springs
+            .iter()
+            .enumerate()
+            .filter(|&(_, &spring)| spring == b'?')
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+
+        (0..1u32 << unknown.len())
+            .filter(|mask| {
+                let mut springs = record.springs.clone();
+                for (bit, &i) in unknown.iter().enumerate() {
+                    springs[i] = if mask & (1 << bit) != 0 { b'#' } else { b'.' };
+                }
+
+                let groups = springs
+                    .split(|&spring| spring == b'.')
+                    .map(<[u8]>::len)
+                    .filter(|&len| len > 0)
+                    .collect::<Vec<_>>();
+                groups == record.groups
+            })
+            .count() as u64
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn matches_brute_force(
+            springs in proptest::collection::vec(proptest::sample::select(b".#?".to_vec()), 0..12),
+            groups in proptest::collection::vec(1..4usize, 0..4),
+        ) {
+            let record = Record { springs, groups };
+            proptest::prop_assert_eq!(
+                record.count_arrangements(),
+                count_arrangements_brute_force(&record)
+            );
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    grid::Grid,
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day13)]
+fn parse_input(input: &str) -> Result<Vec<Pattern>, ParseError> {
+    Cursor::new(13, input)
+        .blocks()
+        .map(|block| {
+            let grid = Grid::parse_tiles(block, ".#", "`.` or `#`")?;
+            if grid.width() > 64 || grid.height() > 64 {
+                return Err(block.error("pattern of at most 64 by 64"));
+            }
+
+            let rows = grid.rows().map(mask).collect();
+            let cols = grid.columns().map(mask).collect();
+
+            Ok(Pattern { rows, cols })
+        })
+        .collect()
+}
+
+/// Returns the bitmask of the rocks in a row or column.
+fn mask<'a>(cells: impl IntoIterator<Item = &'a u8>) -> u64 {
+    cells
+        .into_iter()
+        .fold(0, |mask, &cell| mask << 1 | (cell == b'#') as u64)
+}
+
+#[aoc(day13, part1)]
+fn part1(patterns: &[Pattern]) -> Option<usize> {
+    patterns.iter().map(|pattern| pattern.summarize(0)).sum()
+}
+
+#[aoc(day13, part2)]
+fn part2(patterns: &[Pattern]) -> Option<usize> {
+    patterns.iter().map(|pattern| pattern.summarize(1)).sum()
+}
+
+/// A pattern of ash and rocks, as bitmasks of the rocks in each row and
+/// column.
+#[derive(Clone, Debug)]
+struct Pattern {
+    rows: Vec<u64>,
+    cols: Vec<u64>,
+}
+
+impl Pattern {
+    /// Finds the line of reflection with exactly `smudges` mismatched cells,
+    /// and returns the number of columns to its left if it is vertical, or
+    /// 100 times the number of rows above it if it is horizontal.
+    fn summarize(&self, smudges: u32) -> Option<usize> {
+        reflection(&self.cols, smudges).or_else(|| Some(100 * reflection(&self.rows, smudges)?))
+    }
+}
+
+/// Returns the number of masks before the first line of reflection, such that
+/// the masks on either side of the line differ in exactly `smudges` bits in
+/// total.
+fn reflection(masks: &[u64], smudges: u32) -> Option<usize> {
+    (1..masks.len()).find(|&line| {
+        let before = masks[..line].iter().rev();
+        let after = masks[line..].iter();
+
+        before
+            .zip(after)
+            .map(|(a, b)| (a ^ b).count_ones())
+            .try_fold(0, |total, diff| {
+                Some(total + diff).filter(|&total| total <= smudges)
+            })
+            == Some(smudges)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), Some(405));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), Some(400));
+    }
+
+    #[test]
+    fn no_reflection() {
+        let patterns = parse_input("#.\n..").unwrap();
+        assert_eq!(part1(&patterns), None);
+        assert_eq!(part2(&patterns), Some(1));
+    }
+}
@@ -0,0 +1,126 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    cycle::nth_state,
+    grid::{Dir, Grid, Pos},
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day14)]
+fn parse_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse_tiles(Cursor::new(14, input), "O#.", "`O`, `#` or `.`")
+}
+
+#[aoc(day14, part1)]
+fn part1(grid: &Grid<u8>) -> usize {
+    let mut grid = grid.clone();
+    tilt(&mut grid, Dir::Up);
+    load(&grid)
+}
+
+#[aoc(day14, part2)]
+fn part2(grid: &Grid<u8>) -> usize {
+    let grid = nth_state(grid.clone(), 1_000_000_000, |grid| {
+        let mut grid = grid.clone();
+        spin(&mut grid);
+        grid
+    });
+    load(&grid)
+}
+
+/// Tilts the platform so that all round rocks roll as far as they can in the
+/// direction `dir`.
+fn tilt(grid: &mut Grid<u8>, dir: Dir) {
+    let (lanes, len) = if dir.is_vertical() {
+        (grid.width(), grid.height())
+    } else {
+        (grid.height(), grid.width())
+    };
+
+    for lane in 0..lanes {
+        // position of the i-th tile in the lane, counting from the edge that
+        // the rocks roll towards
+        let pos = |i| match dir {
+            Dir::Up => Pos::new(i, lane),
+            Dir::Down => Pos::new(len - 1 - i, lane),
+            Dir::Left => Pos::new(lane, i),
+            Dir::Right => Pos::new(lane, len - 1 - i),
+        };
+
+        // the first tile that the next round rock can roll to
+        let mut free = 0;
+        for i in 0..len {
+            match grid[pos(i)] {
+                b'#' => free = i + 1,
+                b'O' => {
+                    grid[pos(i)] = b'.';
+                    grid[pos(free)] = b'O';
+                    free += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Tilts the platform north, west, south and then east.
+fn spin(grid: &mut Grid<u8>) {
+    for dir in [Dir::Up, Dir::Left, Dir::Down, Dir::Right] {
+        tilt(grid, dir);
+    }
+}
+
+/// Returns the total load on the north support beams.
+fn load(grid: &Grid<u8>) -> usize {
+    grid.iter()
+        .filter(|&(_, &tile)| tile == b'O')
+        .map(|(pos, _)| grid.height() - pos.row)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 136);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 64);
+    }
+
+    #[test]
+    fn spin_example() {
+        let mut grid = parse_input(EXAMPLE).unwrap();
+        spin(&mut grid);
+        assert_eq!(
+            grid.to_string(),
+            "\
+.....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#...."
+        );
+    }
+}
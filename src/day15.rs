@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use aoc_runner_derive::aoc;
+
+use crate::utils::parse::{Cursor, ParseError};
+
+#[aoc(day15, part1)]
+fn part1(input: &[u8]) -> u32 {
+    input
+        .split(|&b| b == b',')
+        .map(|step| hash(step) as u32)
+        .sum()
+}
+
+#[aoc(day15, part2)]
+fn part2(input: &str) -> Result<usize, ParseError> {
+    let mut boxes = vec![LensBox::default(); 256];
+
+    for mut step in Cursor::new(15, input).delimited(",") {
+        let label = step.take_while(|ch| ch.is_ascii_lowercase()).as_str();
+        if label.is_empty() {
+            return Err(step.error("label"));
+        }
+        let lens_box = &mut boxes[hash(label.as_bytes()) as usize];
+
+        if step.tag("-").is_ok() {
+            step.end()?;
+            lens_box.remove(label);
+        } else {
+            step.tag("=").map_err(|_| step.error("`-` or `=`"))?;
+            let focal_length = step.int()?;
+            step.end()?;
+            lens_box.insert(label, focal_length);
+        }
+    }
+
+    Ok(boxes
+        .iter()
+        .enumerate()
+        .map(|(i, lens_box)| lens_box.focusing_power(i))
+        .sum())
+}
+
+/// The Holiday ASCII String Helper algorithm. Newlines are ignored.
+fn hash(s: &[u8]) -> u8 {
+    s.iter()
+        .filter(|&&b| b != b'\n')
+        .fold(0, |hash, &b| hash.wrapping_add(b).wrapping_mul(17))
+}
+
+/// A box of labelled lenses, in the order they were inserted.
+///
+/// Removed lenses leave an empty slot behind, so that removal does not have
+/// to shift the lenses after it. The slots are compacted once most of them
+/// are empty.
+#[derive(Clone, Debug, Default)]
+struct LensBox<'a> {
+    slots: Vec<Option<Lens<'a>>>,
+    /// The slot index of each lens in the box, by label.
+    index: HashMap<&'a str, usize>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Lens<'a> {
+    label: &'a str,
+    focal_length: u8,
+}
+
+impl<'a> LensBox<'a> {
+    /// Replaces the lens with the given label, keeping its place in the box,
+    /// or adds the lens behind all other lenses if there is none.
+    fn insert(&mut self, label: &'a str, focal_length: u8) {
+        let lens = Lens {
+            label,
+            focal_length,
+        };
+
+        match self.index.get(label) {
+            Some(&i) => self.slots[i] = Some(lens),
+            None => {
+                self.index.insert(label, self.slots.len());
+                self.slots.push(Some(lens));
+            }
+        }
+    }
+
+    /// Removes the lens with the given label, if it is in the box.
+    fn remove(&mut self, label: &str) {
+        let Some(i) = self.index.remove(label) else {
+            return;
+        };
+        self.slots[i] = None;
+
+        if self.index.len() < self.slots.len() / 2 {
+            self.slots.retain(Option::is_some);
+            for (i, lens) in self.slots.iter().flatten().enumerate() {
+                self.index.insert(lens.label, i);
+            }
+        }
+    }
+
+    /// Returns an iterator over the lenses in the box, from front to back.
+    fn lenses(&self) -> impl Iterator<Item = &Lens<'a>> {
+        self.slots.iter().flatten()
+    }
+
+    /// Returns the focusing power of the lenses in the box with the given
+    /// index.
+    fn focusing_power(&self, box_index: usize) -> usize {
+        self.lenses()
+            .enumerate()
+            .map(|(slot, lens)| (box_index + 1) * (slot + 1) * lens.focal_length as usize)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(hash(b"HASH"), 52);
+        assert_eq!(part1(EXAMPLE.as_bytes()), 1320);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(145));
+    }
+
+    #[test]
+    fn lens_box_order() {
+        let mut lens_box = LensBox::default();
+        for (label, focal_length) in [("a", 1), ("b", 2), ("c", 3), ("d", 4)] {
+            lens_box.insert(label, focal_length);
+        }
+        lens_box.remove("a");
+        lens_box.remove("c");
+        lens_box.remove("e");
+        lens_box.insert("b", 5);
+        lens_box.insert("a", 6);
+
+        let lenses = lens_box
+            .lenses()
+            .map(|lens| (lens.label, lens.focal_length))
+            .collect::<Vec<_>>();
+        assert_eq!(lenses, [("b", 5), ("d", 4), ("a", 6)]);
+        assert_eq!(lens_box.focusing_power(1), 2 * (5 + 2 * 4 + 3 * 6));
+    }
+}
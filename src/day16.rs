@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    grid::{Dir, Grid, Pos},
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day16)]
+fn parse_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse_tiles(
+        Cursor::new(16, input),
+        r"./\|-",
+        "`.`, `/`, `\\`, `|` or `-`",
+    )
+}
+
+#[aoc(day16, part1)]
+fn part1(grid: &Grid<u8>) -> usize {
+    count_energized_naive(grid, Pos::new(0, 0), Dir::Right)
+}
+
+#[aoc(day16, part2)]
+fn part2(grid: &Grid<u8>) -> usize {
+    let beams = Beams::new(grid);
+    entry_points(grid)
+        .map(|(pos, dir)| beams.count_energized(pos, dir))
+        .max()
+        .unwrap_or(0)
+}
+
+#[aoc(day16, part2, naive)]
+fn part2_naive(grid: &Grid<u8>) -> usize {
+    entry_points(grid)
+        .map(|(pos, dir)| count_energized_naive(grid, pos, dir))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the tiles on the edges of the grid, with the direction of a beam
+/// entering the grid there.
+fn entry_points(grid: &Grid<u8>) -> impl Iterator<Item = (Pos, Dir)> + '_ {
+    let (width, height) = (grid.width(), grid.height());
+
+    let rows = (0..height).flat_map(move |row| {
+        [
+            (Pos::new(row, 0), Dir::Right),
+            (Pos::new(row, width - 1), Dir::Left),
+        ]
+    });
+    let cols = (0..width).flat_map(move |col| {
+        [
+            (Pos::new(0, col), Dir::Down),
+            (Pos::new(height - 1, col), Dir::Up),
+        ]
+    });
+
+    rows.chain(cols)
+}
+
+/// Returns the directions in which a beam travelling in the direction `dir`
+/// leaves `tile`. The second direction is only present if the beam is split.
+fn deflect(tile: u8, dir: Dir) -> (Dir, Option<Dir>) {
+    match (tile, dir) {
+        (b'/', Dir::Right) | (b'\\', Dir::Left) => (Dir::Up, None),
+        (b'/', Dir::Left) | (b'\\', Dir::Right) => (Dir::Down, None),
+        (b'/', Dir::Up) | (b'\\', Dir::Down) => (Dir::Right, None),
+        (b'/', Dir::Down) | (b'\\', Dir::Up) => (Dir::Left, None),
+        (b'|', Dir::Left | Dir::Right) => (Dir::Up, Some(Dir::Down)),
+        (b'-', Dir::Up | Dir::Down) => (Dir::Left, Some(Dir::Right)),
+        _ => (dir, None),
+    }
+}
+
+/// Counts the energized tiles by simulating a beam entering `pos` in the
+/// direction `dir`.
+fn count_energized_naive(grid: &Grid<u8>, pos: Pos, dir: Dir) -> usize {
+    // bitmask of the directions that beams have entered each tile in
+    let mut seen = Grid::new(grid.width(), grid.height(), 0u8);
+    let mut beams = vec![(pos, dir)];
+
+    while let Some((pos, dir)) = beams.pop() {
+        if seen[pos] & 1 << dir as u8 != 0 {
+            continue;
+        }
+        seen[pos] |= 1 << dir as u8;
+
+        let (a, b) = deflect(grid[pos], dir);
+        for dir in [Some(a), b].into_iter().flatten() {
+            if let Some(next) = grid.step(pos, dir) {
+                beams.push((next, dir));
+            }
+        }
+    }
+
+    seen.iter().filter(|&(_, &dirs)| dirs != 0).count()
+}
+
+/// The tiles energized by the beams leaving each splitter, shared between all
+/// entry points.
+///
+/// Between splitters, a beam follows a single path. Splitters that split a
+/// beam form a graph, with an edge to every splitter that one of their
+/// outgoing beams reaches. The tiles energized by a splitter are the same for
+/// all splitters in a strongly connected component of this graph, and can be
+/// computed for all components from the sinks upwards.
+struct Beams<'a> {
+    grid: &'a Grid<u8>,
+    /// The node index of each splitter.
+    nodes: HashMap<Pos, usize>,
+    /// The tiles energized by a beam split by each splitter, including the
+    /// splitter itself.
+    energized: Vec<TileSet>,
+}
+
+impl<'a> Beams<'a> {
+    fn new(grid: &'a Grid<u8>) -> Self {
+        let splitters = grid
+            .iter()
+            .filter(|&(_, &tile)| matches!(tile, b'|' | b'-'))
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        let nodes = splitters
+            .iter()
+            .enumerate()
+            .map(|(i, &pos)| (pos, i))
+            .collect();
+
+        let mut beams = Self {
+            grid,
+            nodes,
+            energized: Vec::new(),
+        };
+
+        // tiles on the outgoing paths of each splitter, and the splitters
+        // that they end at
+        let mut tiles = Vec::with_capacity(splitters.len());
+        let mut edges = Vec::with_capacity(splitters.len());
+        for &pos in &splitters {
+            let mut set = TileSet::new(grid);
+            set.insert(pos);
+
+            let (a, b) = match grid[pos] {
+                b'|' => (Dir::Up, Dir::Down),
+                _ => (Dir::Left, Dir::Right),
+            };
+            let mut targets = Vec::new();
+            for dir in [a, b] {
+                if let Some(next) = grid.step(pos, dir) {
+                    let (path, target) = beams.trace(next, dir);
+                    set.union_with(&path);
+                    targets.extend(target);
+                }
+            }
+
+            tiles.push(set);
+            edges.push(targets);
+        }
+
+        // components are found in reverse topological order, so the
+        // components they have edges to have always been handled already
+        let components = strongly_connected_components(&edges);
+        let mut component_of = vec![0; splitters.len()];
+        let mut energized = Vec::with_capacity(components.len());
+        for (c, component) in components.iter().enumerate() {
+            let mut set = TileSet::new(grid);
+            for &node in component {
+                component_of[node] = c;
+                set.union_with(&tiles[node]);
+            }
+            for &node in component {
+                for &target in &edges[node] {
+                    if component_of[target] != c {
+                        set.union_with(&energized[component_of[target]]);
+                    }
+                }
+            }
+            energized.push(set);
+        }
+
+        beams.energized = component_of
+            .into_iter()
+            .map(|c| energized[c].clone())
+            .collect();
+        beams
+    }
+
+    /// Follows a beam entering `start` in the direction `dir` until it is
+    /// split or leaves the grid. Returns the tiles it passes through and the
+    /// node of the splitter that splits it.
+    fn trace(&self, start: Pos, start_dir: Dir) -> (TileSet, Option<usize>) {
+        let mut tiles = TileSet::new(self.grid);
+        let (mut pos, mut dir) = (start, start_dir);
+
+        loop {
+            tiles.insert(pos);
+            match deflect(self.grid[pos], dir) {
+                (_, Some(_)) => return (tiles, Some(self.nodes[&pos])),
+                (next, None) => dir = next,
+            }
+
+            match self.grid.step(pos, dir) {
+                Some(next) => pos = next,
+                None => return (tiles, None),
+            }
+
+            // mirrors never merge two beams, so the path can only loop back
+            // to where it started
+            if (pos, dir) == (start, start_dir) {
+                return (tiles, None);
+            }
+        }
+    }
+
+    /// Counts the energized tiles for a beam entering `pos` in the direction
+    /// `dir`.
+    fn count_energized(&self, pos: Pos, dir: Dir) -> usize {
+        match self.trace(pos, dir) {
+            (mut tiles, Some(node)) => {
+                tiles.union_with(&self.energized[node]);
+                tiles.len()
+            }
+            (tiles, None) => tiles.len(),
+        }
+    }
+}
+
+/// Returns the strongly connected components of a graph, given as a list of
+/// edges for each node, in reverse topological order.
+///
+/// This is Tarjan's algorithm.
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct State<'a> {
+        edges: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low_link: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(state: &mut State, node: usize) {
+        state.index[node] = Some(state.next_index);
+        state.low_link[node] = state.next_index;
+        state.next_index += 1;
+        state.stack.push(node);
+        state.on_stack[node] = true;
+
+        for &target in &state.edges[node] {
+            match state.index[target] {
+                None => {
+                    visit(state, target);
+                    state.low_link[node] = state.low_link[node].min(state.low_link[target]);
+                }
+                Some(index) if state.on_stack[target] => {
+                    state.low_link[node] = state.low_link[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(state.low_link[node]) == state.index[node] {
+            let mut component = Vec::new();
+            loop {
+                let member = state.stack.pop().unwrap();
+                state.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            state.components.push(component);
+        }
+    }
+
+    let n = edges.len();
+    let mut state = State {
+        edges,
+        index: vec![None; n],
+        low_link: vec![0; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        next_index: 0,
+        components: Vec::new(),
+    };
+
+    for node in 0..n {
+        if state.index[node].is_none() {
+            visit(&mut state, node);
+        }
+    }
+
+    state.components
+}
+
+/// A set of tiles in a grid, stored as a bitset.
+#[derive(Clone, Debug)]
+struct TileSet {
+    width: usize,
+    words: Vec<u64>,
+}
+
+impl TileSet {
+    fn new<T>(grid: &Grid<T>) -> Self {
+        Self {
+            width: grid.width(),
+            words: vec![0; (grid.width() * grid.height()).div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, pos: Pos) {
+        let i = pos.row * self.width + pos.col;
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 46);
+    }
+
+    #[test]
+    fn part2_example() {
+        let grid = parse_input(EXAMPLE).unwrap();
+        assert_eq!(part2(&grid), 51);
+        assert_eq!(part2_naive(&grid), 51);
+    }
+
+    #[test]
+    fn all_entry_points() {
+        let grid = parse_input(EXAMPLE).unwrap();
+        let beams = Beams::new(&grid);
+        for (pos, dir) in entry_points(&grid) {
+            assert_eq!(
+                beams.count_energized(pos, dir),
+                count_energized_naive(&grid, pos, dir),
+                "{pos:?} {dir:?}"
+            );
+        }
+    }
+}
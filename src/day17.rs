@@ -0,0 +1,94 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    dijkstra::shortest_path,
+    grid::{Dir, Grid, Pos},
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day17)]
+fn parse_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    Ok(Grid::parse_tiles(Cursor::new(17, input), "0123456789", "digit")?.map(|&b| b - b'0'))
+}
+
+#[aoc(day17, part1)]
+fn part1(grid: &Grid<u8>) -> Option<u32> {
+    min_heat_loss(grid, 1, 3)
+}
+
+#[aoc(day17, part2)]
+fn part2(grid: &Grid<u8>) -> Option<u32> {
+    min_heat_loss(grid, 4, 10)
+}
+
+/// Returns the least heat loss on a path from the top left to the bottom
+/// right block, where the crucible has to move at least `min_run` and at most
+/// `max_run` blocks in a straight line before turning.
+fn min_heat_loss(grid: &Grid<u8>, min_run: usize, max_run: usize) -> Option<u32> {
+    let goal = Pos::new(grid.height().checked_sub(1)?, grid.width().checked_sub(1)?);
+
+    // each state is a block and the direction of the last run, since the
+    // next run has to turn. Runs of every allowed length are a single step,
+    // so the length of the current run doesn't need to be tracked.
+    let start = Pos::new(0, 0);
+    let starts = [(start, Dir::Right), (start, Dir::Down)];
+
+    let successors = |&(pos, dir): &(Pos, Dir)| {
+        let mut next = Vec::new();
+        for turn in [dir.turn_left(), dir.turn_right()] {
+            let mut pos = pos;
+            let mut heat_loss = 0;
+            for run in 1..=max_run {
+                let Some(step) = grid.step(pos, turn) else {
+                    break;
+                };
+                pos = step;
+                heat_loss += grid[pos] as u32;
+                if run >= min_run {
+                    next.push(((pos, turn), heat_loss));
+                }
+            }
+        }
+        next
+    };
+
+    shortest_path(starts, successors, |&(pos, _)| pos == goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    const EXAMPLE_2: &str = "\
+111111111111
+999999999991
+999999999991
+999999999991
+999999999991";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE_1).unwrap()), Some(102));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE_1).unwrap()), Some(94));
+        assert_eq!(part2(&parse_input(EXAMPLE_2).unwrap()), Some(71));
+    }
+}
@@ -0,0 +1,125 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    grid::Dir,
+    parse::{Cursor, ParseError},
+    polygon::{boundary_points, interior_points},
+};
+
+#[aoc_generator(day18)]
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    Cursor::new(18, input)
+        .lines()
+        .map(|mut line| {
+            let dir = line.word()?;
+            let dir = match dir.as_str() {
+                "U" => Dir::Up,
+                "D" => Dir::Down,
+                "L" => Dir::Left,
+                "R" => Dir::Right,
+                _ => return Err(dir.error("`U`, `D`, `L` or `R`")),
+            };
+            let dist = line.int()?;
+            line.skip_whitespace();
+
+            let mut color = line.parens()?;
+            line.end()?;
+            color.tag("#")?;
+            let color_dist = color.field(5)?;
+            let color_dir = color.field(1)?;
+            color.end()?;
+
+            let color_dist = color_dist
+                .as_str()
+                .chars()
+                .try_fold(0, |dist, ch| Some(dist * 16 + ch.to_digit(16)? as i64))
+                .ok_or_else(|| color_dist.error("five hexadecimal digits"))?;
+            let color_dir = match color_dir.as_str() {
+                "0" => Dir::Right,
+                "1" => Dir::Down,
+                "2" => Dir::Left,
+                "3" => Dir::Up,
+                _ => return Err(color_dir.error("`0`, `1`, `2` or `3`")),
+            };
+
+            Ok(Instruction {
+                step: Step { dir, dist },
+                color_step: Step {
+                    dir: color_dir,
+                    dist: color_dist,
+                },
+            })
+        })
+        .collect()
+}
+
+#[aoc(day18, part1)]
+fn part1(plan: &[Instruction]) -> i64 {
+    lagoon_size(plan.iter().map(|instr| instr.step))
+}
+
+#[aoc(day18, part2)]
+fn part2(plan: &[Instruction]) -> i64 {
+    lagoon_size(plan.iter().map(|instr| instr.color_step))
+}
+
+/// Returns the number of cubic meters dug out by following the steps and
+/// then digging out the interior.
+fn lagoon_size(steps: impl Iterator<Item = Step>) -> i64 {
+    let mut vertices = Vec::new();
+    let (mut row, mut col) = (0, 0);
+    for step in steps {
+        let (drow, dcol) = step.dir.delta();
+        row += drow as i64 * step.dist;
+        col += dcol as i64 * step.dist;
+        vertices.push((row, col));
+    }
+
+    // the trench is on the boundary of the polygon through the centres of
+    // the dug out cubes
+    interior_points(&vertices) + boundary_points(&vertices)
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Step {
+    dir: Dir,
+    dist: i64,
+}
+
+/// A line of the dig plan, with the step encoded in its colour code.
+#[derive(Copy, Clone, Debug)]
+struct Instruction {
+    step: Step,
+    color_step: Step,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 62);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 952408144115);
+    }
+}
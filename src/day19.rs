@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    interval::Interval,
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day19)]
+fn parse_input(input: &str) -> Result<System, ParseError> {
+    let input = Cursor::new(19, input);
+    let mut blocks = input.blocks();
+    let workflows_block = blocks.next().ok_or_else(|| input.error("workflows"))?;
+    let parts_block = blocks
+        .next()
+        .ok_or_else(|| input.error_at_end("part ratings"))?;
+    if let Some(block) = blocks.next() {
+        return Err(block.error("end of input"));
+    }
+
+    // workflow names have to be known before rules can refer to them
+    let mut names = Vec::new();
+    let mut rules = Vec::new();
+    for line in workflows_block.lines() {
+        let (name, mut rest) = line.split_once("{")?;
+        rules.push(rest.take_until("}")?);
+        rest.end()?;
+        names.push(name);
+    }
+    let indices = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.as_str(), i))
+        .collect::<HashMap<_, _>>();
+
+    let target = |target: Cursor| match target.as_str() {
+        "A" => Ok(Target::Accept),
+        "R" => Ok(Target::Reject),
+        name => indices
+            .get(name)
+            .map(|&i| Target::Workflow(i))
+            .ok_or_else(|| target.error("`A`, `R` or workflow name")),
+    };
+
+    let workflows = rules
+        .into_iter()
+        .map(|rules| {
+            let mut rules = rules.delimited(",").peekable();
+            let mut workflow = Workflow {
+                rules: Vec::new(),
+                fallback: Target::Reject,
+            };
+
+            while let Some(rule) = rules.next() {
+                if rules.peek().is_none() {
+                    workflow.fallback = target(rule)?;
+                    break;
+                }
+
+                let (mut cond, target_name) = rule.split_once(":")?;
+                let category = cond.field(1)?;
+                let category = "xmas"
+                    .find(category.as_str())
+                    .ok_or_else(|| category.error("`x`, `m`, `a` or `s`"))?;
+                let op = cond.field(1)?;
+                let op = match op.as_str() {
+                    "<" => Op::Less,
+                    ">" => Op::Greater,
+                    _ => return Err(op.error("`<` or `>`")),
+                };
+                let value = cond.int()?;
+                cond.end()?;
+
+                workflow.rules.push(Rule {
+                    category,
+                    op,
+                    value,
+                    target: target(target_name)?,
+                });
+            }
+
+            Ok(workflow)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let start = *indices
+        .get("in")
+        .ok_or_else(|| workflows_block.error("workflow named `in`"))?;
+    if let Some(i) = find_cycle(&workflows) {
+        return Err(names[i].error("workflow that is not part of a cycle"));
+    }
+
+    let parts = parts_block
+        .lines()
+        .map(|mut line| {
+            line.tag("{")?;
+            let ratings = line.take_until("}")?;
+            line.end()?;
+
+            let mut fields = ratings.delimited(",");
+            let mut part = [0; 4];
+            for (rating, name) in part.iter_mut().zip(["x=", "m=", "a=", "s="]) {
+                let mut field = fields
+                    .next()
+                    .ok_or_else(|| ratings.error_at_end(format!("`,{name}`")))?;
+                field.tag(name)?;
+                *rating = field.int()?;
+                field.end()?;
+            }
+            if let Some(field) = fields.next() {
+                return Err(field.error("`}`"));
+            }
+
+            Ok(part)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(System {
+        workflows,
+        start,
+        parts,
+    })
+}
+
+/// Returns the index of a workflow on a cycle of workflows, if there is one.
+fn find_cycle(workflows: &[Workflow]) -> Option<usize> {
+    #[derive(Copy, Clone, PartialEq)]
+    enum State {
+        Unvisited,
+        InProgress,
+        Done,
+    }
+
+    fn visit(workflows: &[Workflow], states: &mut [State], i: usize) -> Option<usize> {
+        match states[i] {
+            State::Done => return None,
+            State::InProgress => return Some(i),
+            State::Unvisited => states[i] = State::InProgress,
+        }
+
+        for target in workflows[i].targets() {
+            if let Target::Workflow(j) = target {
+                if let Some(cycle) = visit(workflows, states, j) {
+                    return Some(cycle);
+                }
+            }
+        }
+
+        states[i] = State::Done;
+        None
+    }
+
+    let mut states = vec![State::Unvisited; workflows.len()];
+    (0..workflows.len()).find_map(|i| visit(workflows, &mut states, i))
+}
+
+#[aoc(day19, part1)]
+fn part1(system: &System) -> u64 {
+    system
+        .parts
+        .iter()
+        .filter(|part| system.accepts(part))
+        .map(|part| part.iter().sum::<u64>())
+        .sum()
+}
+
+#[aoc(day19, part2)]
+fn part2(system: &System) -> u64 {
+    let all = Interval::new(1, 4001);
+    system.count_accepted([all; 4])
+}
+
+/// The ratings of a part in each category, in the order `x`, `m`, `a`, `s`.
+type Part = [u64; 4];
+
+/// A range of ratings in each category.
+type PartRange = [Interval<u64>; 4];
+
+#[derive(Clone, Debug)]
+struct System {
+    workflows: Vec<Workflow>,
+    /// The index of the workflow named `in`.
+    start: usize,
+    parts: Vec<Part>,
+}
+
+impl System {
+    fn accepts(&self, part: &Part) -> bool {
+        let mut workflow = &self.workflows[self.start];
+        loop {
+            let target = workflow
+                .rules
+                .iter()
+                .find(|rule| rule.matches(part[rule.category]))
+                .map_or(workflow.fallback, |rule| rule.target);
+
+            match target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(i) => workflow = &self.workflows[i],
+            }
+        }
+    }
+
+    /// Returns the number of parts in `range` that are accepted.
+    fn count_accepted(&self, range: PartRange) -> u64 {
+        let mut count = 0;
+        let mut queue = vec![(Target::Workflow(self.start), range)];
+
+        while let Some((target, mut range)) = queue.pop() {
+            let workflow = match target {
+                Target::Accept => {
+                    count += range.iter().map(Interval::len).product::<u64>();
+                    continue;
+                }
+                Target::Reject => continue,
+                Target::Workflow(i) => &self.workflows[i],
+            };
+
+            // each rule splits off the parts it matches, and passes the rest
+            // on to the next rule
+            for rule in &workflow.rules {
+                let (matching, rest) = rule.split(range[rule.category]);
+                if !matching.is_empty() {
+                    let mut matching_range = range;
+                    matching_range[rule.category] = matching;
+                    queue.push((rule.target, matching_range));
+                }
+                range[rule.category] = rest;
+            }
+
+            if range.iter().all(|interval| !interval.is_empty()) {
+                queue.push((workflow.fallback, range));
+            }
+        }
+
+        count
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Workflow {
+    rules: Vec<Rule>,
+    /// Where parts go that don't match any rule.
+    fallback: Target,
+}
+
+impl Workflow {
+    fn targets(&self) -> impl Iterator<Item = Target> + '_ {
+        self.rules
+            .iter()
+            .map(|rule| rule.target)
+            .chain([self.fallback])
+    }
+}
+
+/// A rule sending parts to `target` if their rating in `category` compares
+/// to `value` by `op`.
+#[derive(Copy, Clone, Debug)]
+struct Rule {
+    category: usize,
+    op: Op,
+    value: u64,
+    target: Target,
+}
+
+impl Rule {
+    fn matches(&self, rating: u64) -> bool {
+        match self.op {
+            Op::Less => rating < self.value,
+            Op::Greater => rating > self.value,
+        }
+    }
+
+    /// Splits a range of ratings into the ratings that match the rule and
+    /// those that don't.
+    fn split(&self, ratings: Interval<u64>) -> (Interval<u64>, Interval<u64>) {
+        match self.op {
+            Op::Less => ratings.split_at(self.value),
+            Op::Greater => {
+                let (below, above) = ratings.split_at(self.value + 1);
+                (above, below)
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Op {
+    Less,
+    Greater,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 19114);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 167409079868000);
+    }
+
+    #[test]
+    fn count_accepted_matches_accepts() {
+        // every part with ratings of 1 or 4000 in each category
+        let system = parse_input(EXAMPLE).unwrap();
+        for mask in 0..16 {
+            let part = [0, 1, 2, 3].map(|i| if mask & (1 << i) != 0 { 4000 } else { 1 });
+            let range = part.map(|rating| Interval::new(rating, rating + 1));
+            assert_eq!(system.count_accepted(range), system.accepts(&part) as u64);
+        }
+    }
+
+    #[test]
+    fn cyclic_workflows() {
+        let err = parse_input("in{x<10:a,R}\na{in}\n\n{x=1,m=2,a=3,s=4}").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
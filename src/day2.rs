@@ -1,32 +1,36 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::utils::parse::{Cursor, ParseError};
+
 #[aoc_generator(day2)]
-fn parse_input(input: &str) -> Vec<Game> {
-    input
+fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    Cursor::new(2, input)
         .lines()
         .map(|line| {
-            let (game_id, subsets) = line.split_once(": ").unwrap();
-            let id = game_id.strip_prefix("Game ").unwrap().parse().unwrap();
+            let (mut game, subsets) = line.key_value()?;
+            game.tag("Game ")?;
+            let id = game.int()?;
+            game.end()?;
 
             let subsets = subsets
-                .split(';')
+                .delimited(";")
                 .map(|subset| {
                     let mut colors = Colors::default();
-                    for color in subset.trim().split(',') {
-                        let (count, color) = color.trim().split_once(' ').unwrap();
-                        let count = count.parse().unwrap();
-                        match color {
+                    for mut color in subset.delimited(",") {
+                        let count = color.int()?;
+                        color.tag(" ")?;
+                        match color.as_str() {
                             "red" => colors.red = count,
                             "green" => colors.green = count,
                             "blue" => colors.blue = count,
-                            _ => panic!("unknown color: {color}"),
+                            _ => return Err(color.error("`red`, `green` or `blue`")),
                         }
                     }
-                    colors
+                    Ok(colors)
                 })
-                .collect();
+                .collect::<Result<_, _>>()?;
 
-            Game { id, subsets }
+            Ok(Game { id, subsets })
         })
         .collect()
 }
@@ -74,3 +78,33 @@ struct Colors {
     green: u32,
     blue: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 8);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 2286);
+    }
+
+    #[test]
+    fn truncated_line() {
+        let err = parse_input("Game 1: 3 red\nGame 2: 1 blue, 2")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column), (2, 18));
+    }
+}
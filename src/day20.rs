@@ -0,0 +1,556 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt::{self, Display},
+};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    num::lcm,
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day20)]
+fn parse_input(input: &str) -> Result<Circuit, ParseError> {
+    let lines = Cursor::new(20, input)
+        .lines()
+        .map(|line| {
+            let (mut source, destinations) = line.split_once(" -> ")?;
+            let kind = if source.tag("%").is_ok() {
+                Kind::FlipFlop
+            } else if source.tag("&").is_ok() {
+                Kind::Conjunction
+            } else if source.as_str() == "broadcaster" {
+                Kind::Broadcaster
+            } else {
+                return Err(source.error("`%`, `&` or `broadcaster`"));
+            };
+
+            Ok((source, kind, destinations))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut modules = Vec::new();
+    let mut indices = HashMap::new();
+    for &(name, kind, _) in &lines {
+        if indices.insert(name.as_str(), modules.len()).is_some() {
+            return Err(name.error("unique module name"));
+        }
+        modules.push(Module {
+            name: name.as_str().to_owned(),
+            kind,
+            outputs: Vec::new(),
+            inputs: Vec::new(),
+        });
+    }
+
+    for (i, &(_, _, destinations)) in lines.iter().enumerate() {
+        for destination in destinations.delimited(",") {
+            if destination.is_empty() {
+                return Err(destination.error("module name"));
+            }
+
+            // modules that are only ever sent pulses, such as `rx`, don't
+            // have a line of their own
+            let j = *indices.entry(destination.as_str()).or_insert_with(|| {
+                modules.push(Module {
+                    name: destination.as_str().to_owned(),
+                    kind: Kind::Output,
+                    outputs: Vec::new(),
+                    inputs: Vec::new(),
+                });
+                modules.len() - 1
+            });
+
+            let slot = modules[j].inputs.len();
+            modules[j].inputs.push(i);
+            modules[i].outputs.push((j, slot));
+        }
+    }
+
+    let broadcaster = *indices
+        .get("broadcaster")
+        .ok_or_else(|| Cursor::new(20, input).error("`broadcaster` module"))?;
+
+    Ok(Circuit {
+        modules,
+        broadcaster,
+    })
+}
+
+#[aoc(day20, part1)]
+fn part1(circuit: &Circuit) -> u64 {
+    let mut state = State::new(circuit);
+    let (mut low, mut high) = (0, 0);
+
+    for _ in 0..1000 {
+        state.press(|pulse| {
+            if pulse.high {
+                high += 1;
+            } else {
+                low += 1;
+            }
+        });
+    }
+
+    low * high
+}
+
+#[aoc(day20, part2)]
+fn part2(circuit: &Circuit) -> Result<Presses, UnsupportedCircuit> {
+    Ok(Presses(circuit.analyze_counters()?))
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// A module without outputs of its own, that only receives pulses.
+    Output,
+}
+
+#[derive(Clone, Debug)]
+struct Module {
+    name: String,
+    kind: Kind,
+    /// The modules this module sends pulses to, with the index of this module
+    /// in their `inputs`.
+    outputs: Vec<(usize, usize)>,
+    inputs: Vec<usize>,
+}
+
+impl Module {
+    fn sends_to(&self, module: usize) -> bool {
+        self.outputs.iter().any(|&(output, _)| output == module)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Circuit {
+    modules: Vec<Module>,
+    broadcaster: usize,
+}
+
+/// A binary counter in the circuit, which makes its hub send a low pulse
+/// every `period` button presses.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Counter {
+    hub: String,
+    period: u64,
+}
+
+/// The number of button presses until `rx` receives a low pulse, given by the
+/// counters feeding it.
+///
+/// Formats as the number of presses, which is all that `cargo aoc` prints.
+/// With `{:#}` it also lists the period of each counter, which can also be
+/// read directly from [`Circuit::analyze_counters`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Presses(Vec<Counter>);
+
+impl Presses {
+    fn total(&self) -> u64 {
+        self.0.iter().map(|counter| counter.period).fold(1, lcm)
+    }
+}
+
+impl Display for Presses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.total())?;
+        if f.alternate() {
+            let periods = self
+                .0
+                .iter()
+                .map(|counter| format!("`{}` every {}", counter.hub, counter.period))
+                .collect::<Vec<_>>();
+            write!(f, " ({})", periods.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// An error returned when the part of the circuit feeding `rx` is not made
+/// up of binary counters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct UnsupportedCircuit(String);
+
+impl Display for UnsupportedCircuit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day 20: unsupported circuit: {}", self.0)
+    }
+}
+
+impl Error for UnsupportedCircuit {}
+
+impl Circuit {
+    fn module(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|module| module.name == name)
+    }
+
+    fn is_conjunction(&self, module: usize) -> bool {
+        self.modules[module].kind == Kind::Conjunction
+    }
+
+    /// Finds the binary counters feeding `rx`.
+    ///
+    /// `rx` has to be fed by a single conjunction, whose inputs are inverters
+    /// (conjunctions with a single input). Each inverter has to be fed by the
+    /// hub of a counter, see [`analyze_counter`](Self::analyze_counter).
+    /// `rx` then receives a low pulse once every counter's hub sends a low
+    /// pulse in the same button press.
+    ///
+    /// This is the entry point for inspecting the circuit: the returned
+    /// counters name each hub and its period, while part 2 only reports the
+    /// number of presses.
+    fn analyze_counters(&self) -> Result<Vec<Counter>, UnsupportedCircuit> {
+        let err = |message: String| Err(UnsupportedCircuit(message));
+        let name = |module: usize| &self.modules[module].name;
+
+        let Some(rx) = self.module("rx") else {
+            return err("there is no module named `rx`".to_owned());
+        };
+        let &[last] = &self.modules[rx].inputs[..] else {
+            return err("`rx` should have exactly one input".to_owned());
+        };
+        if !self.is_conjunction(last) {
+            return err(format!(
+                "`{}`, the input of `rx`, should be a conjunction",
+                name(last)
+            ));
+        }
+
+        let mut counters = Vec::new();
+        for &inverter in &self.modules[last].inputs {
+            let &[hub] = &self.modules[inverter].inputs[..] else {
+                return err(format!(
+                    "`{}` should have exactly one input",
+                    name(inverter)
+                ));
+            };
+            if !self.is_conjunction(inverter) || !self.is_conjunction(hub) {
+                return err(format!(
+                    "`{}` and its input `{}` should both be conjunctions",
+                    name(inverter),
+                    name(hub)
+                ));
+            }
+
+            counters.push(self.analyze_counter(hub)?);
+        }
+
+        Ok(counters)
+    }
+
+    /// Analyzes the binary counter with the given hub.
+    ///
+    /// A counter is a chain of flip-flops, starting at an output of the
+    /// broadcaster, where each flip-flop is the next bit of the counter.
+    /// Every button press increments the counter. The hub conjunction is fed
+    /// by the bits that are set in the counter's period, so it sends a low
+    /// pulse once the counter reaches the period. This pulse goes to the
+    /// lowest bit and to every bit that is not set in the period, which
+    /// overflows the counter back to zero.
+    fn analyze_counter(&self, hub: usize) -> Result<Counter, UnsupportedCircuit> {
+        let err = |message: String| Err(UnsupportedCircuit(message));
+        let name = |module: usize| &self.modules[module].name;
+        let hub_module = &self.modules[hub];
+
+        let first = self.modules[self.broadcaster]
+            .outputs
+            .iter()
+            .map(|&(output, _)| output)
+            .filter(|&output| hub_module.sends_to(output))
+            .collect::<Vec<_>>();
+        let &[first] = &first[..] else {
+            return err(format!(
+                "`{}` should send pulses to exactly one output of the broadcaster",
+                name(hub)
+            ));
+        };
+
+        let mut bits = vec![first];
+        loop {
+            let bit = &self.modules[*bits.last().unwrap()];
+            if bit.kind != Kind::FlipFlop {
+                return err(format!("`{}` should be a flip-flop", bit.name));
+            }
+            if bits.len() > 63 {
+                return err(format!("the counter of `{}` has too many bits", name(hub)));
+            }
+
+            let next = bit
+                .outputs
+                .iter()
+                .map(|&(output, _)| output)
+                .filter(|&output| output != hub)
+                .collect::<Vec<_>>();
+            match next[..] {
+                [] => break,
+                [next] if !bits.contains(&next) => bits.push(next),
+                _ => {
+                    return err(format!(
+                        "`{}` should send pulses to at most one other flip-flop besides `{}`",
+                        bit.name,
+                        name(hub)
+                    ))
+                }
+            }
+        }
+
+        let mut period = 0u64;
+        for (i, &bit) in bits.iter().enumerate() {
+            let set = self.modules[bit].sends_to(hub);
+            if set {
+                period |= 1 << i;
+            }
+
+            // the hub resets the counter through the lowest bit and the bits
+            // that are not set
+            let resets = i == 0 || !set;
+            if hub_module.sends_to(bit) != resets {
+                return err(if resets {
+                    format!(
+                        "`{}` should reset its counter through `{}`",
+                        name(hub),
+                        name(bit)
+                    )
+                } else {
+                    format!("`{}` should not send pulses to `{}`", name(hub), name(bit))
+                });
+            }
+
+            // the previous bit, and the hub if it resets this bit
+            let mut expected_inputs = vec![if i == 0 {
+                self.broadcaster
+            } else {
+                bits[i - 1]
+            }];
+            if resets {
+                expected_inputs.push(hub);
+            }
+            let mut inputs = self.modules[bit].inputs.clone();
+            expected_inputs.sort_unstable();
+            inputs.sort_unstable();
+            if inputs != expected_inputs {
+                return err(format!("`{}` has unexpected inputs", name(bit)));
+            }
+        }
+
+        if hub_module.inputs.len() != period.count_ones() as usize {
+            return err(format!(
+                "`{}` should only have inputs from its counter",
+                name(hub)
+            ));
+        }
+        if period & 1 == 0 {
+            return err(format!(
+                "the lowest bit of the counter of `{}` should be set",
+                name(hub)
+            ));
+        }
+        if hub_module.outputs.len() != bits.len() - period.count_ones() as usize + 2 {
+            return err(format!(
+                "`{}` should only send pulses to its counter and one inverter",
+                name(hub)
+            ));
+        }
+
+        Ok(Counter {
+            hub: name(hub).clone(),
+            period,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Pulse {
+    to: usize,
+    /// The index of the sending module in the inputs of `to`.
+    slot: usize,
+    high: bool,
+}
+
+/// The state of every module in a circuit.
+struct State<'a> {
+    circuit: &'a Circuit,
+    /// Whether each flip-flop is on.
+    on: Vec<bool>,
+    /// Whether the last pulse a conjunction received from each of its inputs
+    /// was high.
+    memory: Vec<Vec<bool>>,
+    /// The number of high pulses in each conjunction's memory.
+    high_inputs: Vec<usize>,
+    queue: VecDeque<Pulse>,
+}
+
+impl<'a> State<'a> {
+    fn new(circuit: &'a Circuit) -> Self {
+        Self {
+            circuit,
+            on: vec![false; circuit.modules.len()],
+            memory: circuit
+                .modules
+                .iter()
+                .map(|module| vec![false; module.inputs.len()])
+                .collect(),
+            high_inputs: vec![0; circuit.modules.len()],
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Pushes the button, and calls `observe` for every pulse sent, in order,
+    /// including the low pulse from the button to the broadcaster.
+    fn press(&mut self, mut observe: impl FnMut(&Pulse)) {
+        self.queue.push_back(Pulse {
+            to: self.circuit.broadcaster,
+            slot: 0,
+            high: false,
+        });
+
+        while let Some(pulse) = self.queue.pop_front() {
+            observe(&pulse);
+
+            let module = &self.circuit.modules[pulse.to];
+            let high = match module.kind {
+                Kind::Broadcaster => pulse.high,
+                Kind::FlipFlop if pulse.high => continue,
+                Kind::FlipFlop => {
+                    self.on[pulse.to] ^= true;
+                    self.on[pulse.to]
+                }
+                Kind::Conjunction => {
+                    let remembered = &mut self.memory[pulse.to][pulse.slot];
+                    if *remembered != pulse.high {
+                        *remembered = pulse.high;
+                        if pulse.high {
+                            self.high_inputs[pulse.to] += 1;
+                        } else {
+                            self.high_inputs[pulse.to] -= 1;
+                        }
+                    }
+                    self.high_inputs[pulse.to] != module.inputs.len()
+                }
+                Kind::Output => continue,
+            };
+
+            for &(to, slot) in &module.outputs {
+                self.queue.push_back(Pulse { to, slot, high });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "\
+broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const EXAMPLE_2: &str = "\
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE_1).unwrap()), 32000000);
+        assert_eq!(part1(&parse_input(EXAMPLE_2).unwrap()), 11687500);
+    }
+
+    /// Returns the lines of a counter with the given period, named after
+    /// `prefix`, which feeds the conjunction `last`.
+    fn counter(prefix: char, bits: usize, period: u64) -> Vec<String> {
+        let hub = format!("{prefix}h");
+        let mut lines = Vec::new();
+        let mut hub_outputs = vec![format!("{prefix}i")];
+
+        for i in 0..bits {
+            let set = period & (1 << i) != 0;
+            let mut outputs = Vec::new();
+            if i + 1 < bits {
+                outputs.push(format!("{prefix}{}", i + 1));
+            }
+            if set {
+                outputs.push(hub.clone());
+            }
+            if i == 0 || !set {
+                hub_outputs.push(format!("{prefix}{i}"));
+            }
+            lines.push(format!("%{prefix}{i} -> {}", outputs.join(", ")));
+        }
+
+        lines.push(format!("&{hub} -> {}", hub_outputs.join(", ")));
+        lines.push(format!("&{prefix}i -> last"));
+        lines
+    }
+
+    fn counters_circuit(counters: &[(char, usize, u64)]) -> String {
+        let mut lines = vec![format!(
+            "broadcaster -> {}",
+            counters
+                .iter()
+                .map(|(prefix, _, _)| format!("{prefix}0"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )];
+        for &(prefix, bits, period) in counters {
+            lines.extend(counter(prefix, bits, period));
+        }
+        lines.push("&last -> rx".to_owned());
+        lines.join("\n")
+    }
+
+    #[test]
+    fn analyze_counters() {
+        let circuit = parse_input(&counters_circuit(&[('a', 4, 11), ('b', 5, 29)])).unwrap();
+        let counters = circuit.analyze_counters().unwrap();
+        assert_eq!(
+            counters,
+            [
+                Counter {
+                    hub: "ah".to_owned(),
+                    period: 11
+                },
+                Counter {
+                    hub: "bh".to_owned(),
+                    period: 29
+                },
+            ]
+        );
+
+        // simulating the circuit gives the same answer
+        let rx = circuit.module("rx").unwrap();
+        let mut state = State::new(&circuit);
+        let presses = (1..)
+            .find(|_| {
+                let mut low = false;
+                state.press(|pulse| low |= pulse.to == rx && !pulse.high);
+                low
+            })
+            .unwrap();
+        assert_eq!(presses, 11 * 29);
+        let presses = part2(&circuit).unwrap();
+        assert_eq!(presses.total(), 11 * 29);
+        assert_eq!(presses.to_string(), "319");
+        assert_eq!(format!("{presses:#}"), "319 (`ah` every 11, `bh` every 29)");
+    }
+
+    #[test]
+    fn unsupported_circuit() {
+        assert!(part2(&parse_input(EXAMPLE_2).unwrap()).is_err());
+
+        // the second bit should be reset by the hub
+        let input = counters_circuit(&[('a', 4, 11)]).replace("&ah -> ai, a0, a2", "&ah -> ai, a0");
+        let err = part2(&parse_input(&input).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("`a2`"), "{err}");
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    grid::{Dir, Grid, Pos},
+    parse::{Cursor, ParseError},
+    sequence::extrapolate_n,
+};
+
+#[aoc_generator(day21)]
+fn parse_input(input: &str) -> Result<Garden, ParseError> {
+    let input = Cursor::new(21, input);
+
+    let grid = Grid::parse_tiles(input, ".#S", "`.`, `#` or `S`")?;
+    // the infinite garden repeats with the same period in both directions
+    if grid.width() != grid.height() {
+        return Err(input.error("square garden"));
+    }
+    let start = grid
+        .position(|&tile| tile == b'S')
+        .ok_or_else(|| input.error("`S` tile"))?;
+
+    Ok(Garden {
+        rocks: grid.map(|&tile| tile == b'#'),
+        start,
+    })
+}
+
+#[aoc(day21, part1)]
+fn part1(garden: &Garden) -> u64 {
+    garden.count_reachable(64)
+}
+
+#[aoc(day21, part2)]
+fn part2(garden: &Garden) -> u64 {
+    garden.count_reachable_extrapolated(26_501_365)
+}
+
+#[derive(Clone, Debug)]
+struct Garden {
+    rocks: Grid<bool>,
+    start: Pos,
+}
+
+impl Garden {
+    /// Returns the number of garden plots that can be reached in exactly
+    /// `steps` steps.
+    fn count_reachable(&self, steps: usize) -> u64 {
+        let mut dist = Grid::new(self.rocks.width(), self.rocks.height(), None);
+        dist[self.start] = Some(0);
+        let mut queue = VecDeque::from([self.start]);
+
+        while let Some(pos) = queue.pop_front() {
+            let d = dist[pos].unwrap();
+            if d == steps {
+                continue;
+            }
+
+            for next in self.rocks.neighbours4(pos) {
+                if !self.rocks[next] && dist[next].is_none() {
+                    dist[next] = Some(d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        // a plot reached in fewer steps can be reached again by stepping
+        // back and forth, as long as the parity matches
+        dist.iter()
+            .filter(|&(_, &d)| d.is_some_and(|d| d % 2 == steps % 2))
+            .count() as u64
+    }
+
+    /// Returns the number of garden plots that can be reached in exactly
+    /// `steps` steps for each number of steps in `samples`, when the garden
+    /// is repeated infinitely in every direction.
+    fn count_reachable_infinite(&self, samples: &[usize]) -> Vec<u64> {
+        let max_steps = samples.iter().copied().max().unwrap_or(0);
+        let (width, height) = (self.rocks.width() as i64, self.rocks.height() as i64);
+
+        // visited tiles in the square of tiles within `max_steps` of the
+        // start, which is all that can be reached
+        let side = 2 * max_steps + 1;
+        let mut visited = vec![false; side * side];
+        let index = |(row, col): (i64, i64)| {
+            let row = usize::try_from(row + max_steps as i64).ok()?;
+            let col = usize::try_from(col + max_steps as i64).ok()?;
+            (row < side && col < side).then_some(row * side + col)
+        };
+
+        let mut counts = vec![0; samples.len()];
+        let mut queue = VecDeque::from([((0, 0), 0)]);
+        visited[index((0, 0)).unwrap()] = true;
+
+        while let Some(((row, col), d)) = queue.pop_front() {
+            for (count, &steps) in counts.iter_mut().zip(samples) {
+                if d <= steps && d % 2 == steps % 2 {
+                    *count += 1;
+                }
+            }
+            if d == max_steps {
+                continue;
+            }
+
+            for dir in Dir::ALL {
+                let (drow, dcol) = dir.delta();
+                let next = (row + drow as i64, col + dcol as i64);
+                let tile = Pos::new(
+                    (self.start.row as i64 + next.0).rem_euclid(height) as usize,
+                    (self.start.col as i64 + next.1).rem_euclid(width) as usize,
+                );
+                if self.rocks[tile] {
+                    continue;
+                }
+
+                let i = index(next).unwrap();
+                if !visited[i] {
+                    visited[i] = true;
+                    queue.push_back((next, d + 1));
+                }
+            }
+        }
+
+        counts
+    }
+
+    /// Returns the number of garden plots that can be reached in exactly
+    /// `steps` steps when the garden is repeated infinitely in every
+    /// direction.
+    ///
+    /// Every time the number of steps grows by the size of the garden, the
+    /// reachable area grows by another ring of copies of the garden, so the
+    /// count eventually grows quadratically. This samples the count at steps
+    /// spaced apart by the size of the garden until the samples fit a
+    /// quadratic, and extrapolates from there.
+    fn count_reachable_extrapolated(&self, steps: usize) -> u64 {
+        // how many second differences of the samples must agree before the
+        // count is taken to be quadratic
+        const STABLE_DIFFERENCES: usize = 4;
+
+        let size = self.rocks.width();
+        let (n, rem) = (steps / size, steps % size);
+
+        // How long the count takes to become quadratic depends on the garden.
+        // With open paths from the start to the edges, as in the puzzle
+        // input, it is quadratic right away, but it takes a few copies of the
+        // garden for the example. Sample more until it settles.
+        let mut periods = STABLE_DIFFERENCES + 1;
+        loop {
+            let samples = (0..=n.min(periods))
+                .map(|k| rem + k * size)
+                .collect::<Vec<_>>();
+            let counts = self.count_reachable_infinite(&samples);
+            if n <= periods {
+                return counts[n];
+            }
+
+            let diffs = counts
+                .windows(3)
+                .map(|w| w[2] as i64 - 2 * w[1] as i64 + w[0] as i64)
+                .collect::<Vec<_>>();
+            let first = diffs
+                .windows(STABLE_DIFFERENCES)
+                .position(|w| w.iter().all(|&d| d == w[0]));
+            if let Some(first) = first {
+                let mut counts = counts[first..first + 3].to_vec();
+                return extrapolate_n(&mut counts, n - first - 2);
+            }
+
+            periods *= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(parse_input(EXAMPLE).unwrap().count_reachable(6), 16);
+    }
+
+    #[test]
+    fn infinite_example() {
+        let garden = parse_input(EXAMPLE).unwrap();
+        assert_eq!(
+            garden.count_reachable_infinite(&[6, 10, 50, 100, 500]),
+            [16, 50, 1594, 6536, 167004]
+        );
+    }
+
+    #[test]
+    fn extrapolated_example() {
+        let garden = parse_input(EXAMPLE).unwrap();
+        assert_eq!(garden.count_reachable_extrapolated(500), 167004);
+        assert_eq!(garden.count_reachable_extrapolated(1000), 668697);
+        assert_eq!(garden.count_reachable_extrapolated(5000), 16733044);
+        assert_eq!(garden.count_reachable_extrapolated(50), 1594);
+    }
+
+    #[test]
+    fn non_square_garden() {
+        let err = parse_input("...\n.S.").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
@@ -0,0 +1,203 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    grid::{Grid, Pos},
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day22)]
+fn parse_input(input: &str) -> Result<Vec<Brick>, ParseError> {
+    let mut bricks = Cursor::new(22, input)
+        .lines()
+        .map(|line| {
+            let (start, end) = line.split_once("~")?;
+            let coords = |mut cursor: Cursor| {
+                let mut coords = [0; 3];
+                for (i, coord) in coords.iter_mut().enumerate() {
+                    if i > 0 {
+                        cursor.tag(",")?;
+                    }
+                    *coord = cursor.int()?;
+                }
+                cursor.end()?;
+                Ok::<_, ParseError>(coords)
+            };
+            let (start, end) = (coords(start)?, coords(end)?);
+
+            Ok(Brick {
+                min: [0, 1, 2].map(|i| start[i].min(end[i])),
+                max: [0, 1, 2].map(|i| start[i].max(end[i])),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // bricks can only land on bricks below them
+    bricks.sort_by_key(|brick| brick.min[2]);
+    Ok(bricks)
+}
+
+#[aoc(day22, part1)]
+fn part1(bricks: &[Brick]) -> usize {
+    let supporters = settle(bricks);
+
+    // a brick can't be removed if it is the only support of another brick
+    let mut needed = vec![false; bricks.len()];
+    for brick_supporters in &supporters {
+        if let &[Some(only)] = &brick_supporters[..] {
+            needed[only] = true;
+        }
+    }
+
+    needed.iter().filter(|&&needed| !needed).count()
+}
+
+#[aoc(day22, part2)]
+fn part2(bricks: &[Brick]) -> usize {
+    let supporters = settle(bricks);
+
+    // A brick falls when another brick is removed iff every path of support
+    // from the ground to it goes through the removed brick, i.e. the removed
+    // brick dominates it in the support graph with the ground as its root.
+    // Bricks are settled in topological order, so the immediate dominator of
+    // a brick is the lowest common ancestor of its supporters in the
+    // dominator tree of the bricks before it.
+    let mut tree = DominatorTree::default();
+    for brick_supporters in &supporters {
+        let idom = brick_supporters
+            .iter()
+            .map(|&supporter| tree.node(supporter))
+            .reduce(|a, b| tree.lca(a, b))
+            .unwrap_or(DominatorTree::ROOT);
+        tree.push(idom);
+    }
+
+    // each brick falls when any of its dominators other than the ground is
+    // removed, which counts every brick once for each of them
+    tree.depth[1..].iter().map(|&depth| depth - 1).sum()
+}
+
+/// Lets the bricks fall, in order of their lowest point, and returns the
+/// bricks (or `None` for the ground) that each brick comes to rest on.
+fn settle(bricks: &[Brick]) -> Vec<Vec<Option<usize>>> {
+    let width = bricks
+        .iter()
+        .map(|brick| brick.max[0] + 1)
+        .max()
+        .unwrap_or(0);
+    let height = bricks
+        .iter()
+        .map(|brick| brick.max[1] + 1)
+        .max()
+        .unwrap_or(0);
+
+    // the height of the top of the stack on each column, and the brick at
+    // the top of it
+    let mut tops = Grid::new(width, height, (0, None));
+    let mut supporters = Vec::with_capacity(bricks.len());
+
+    for (i, brick) in bricks.iter().enumerate() {
+        let footprint = || {
+            (brick.min[1]..=brick.max[1])
+                .flat_map(|y| (brick.min[0]..=brick.max[0]).map(move |x| Pos::new(y, x)))
+        };
+
+        let rest = footprint().map(|pos| tops[pos].0).max().unwrap_or(0);
+        let mut below = footprint()
+            .filter(|&pos| tops[pos].0 == rest)
+            .map(|pos| tops[pos].1)
+            .collect::<Vec<_>>();
+        below.sort_unstable();
+        below.dedup();
+
+        let top = rest + brick.max[2] - brick.min[2] + 1;
+        for pos in footprint() {
+            tops[pos] = (top, Some(i));
+        }
+        supporters.push(below);
+    }
+
+    supporters
+}
+
+/// A dominator tree over the ground and the settled bricks, built in
+/// topological order.
+#[derive(Clone, Debug)]
+struct DominatorTree {
+    /// The immediate dominator of each node, where node 0 is the ground and
+    /// node `i + 1` is brick `i`.
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+}
+
+impl Default for DominatorTree {
+    fn default() -> Self {
+        Self {
+            parent: vec![Self::ROOT],
+            depth: vec![0],
+        }
+    }
+}
+
+impl DominatorTree {
+    const ROOT: usize = 0;
+
+    fn node(&self, supporter: Option<usize>) -> usize {
+        supporter.map_or(Self::ROOT, |brick| brick + 1)
+    }
+
+    fn push(&mut self, parent: usize) {
+        self.parent.push(parent);
+        self.depth.push(self.depth[parent] + 1);
+    }
+
+    fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        while a != b {
+            if self.depth[a] >= self.depth[b] {
+                a = self.parent[a];
+            } else {
+                b = self.parent[b];
+            }
+        }
+        a
+    }
+}
+
+/// A brick, given by the coordinates of its lowest and highest corner.
+#[derive(Copy, Clone, Debug)]
+struct Brick {
+    min: [usize; 3],
+    max: [usize; 3],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 5);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 7);
+    }
+
+    #[test]
+    fn part2_shared_supports() {
+        // a supports b and c, which both support d, which supports e. Removing
+        // a makes all four fall, and removing d makes e fall.
+        let bricks =
+            parse_input("0,0,1~2,0,1\n0,0,2~0,0,2\n2,0,2~2,0,2\n0,0,3~2,0,3\n1,0,4~1,0,4").unwrap();
+        assert_eq!(part2(&bricks), 5);
+    }
+}
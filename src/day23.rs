@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    grid::{Dir, Grid, Pos},
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day23)]
+fn parse_input(input: &str) -> Result<Trails, ParseError> {
+    let input = Cursor::new(23, input);
+
+    let grid = Grid::parse_tiles(input, "#.^>v<", "`#`, `.`, `^`, `>`, `v` or `<`")?;
+    let open_in_row = |row: usize| {
+        (0..grid.width())
+            .map(|col| Pos::new(row, col))
+            .find(|&pos| grid[pos] == b'.')
+    };
+    let start = open_in_row(0).ok_or_else(|| input.error("path in the top row"))?;
+    let goal = open_in_row(grid.height() - 1)
+        .ok_or_else(|| input.error_at_end("path in the bottom row"))?;
+
+    // the start and goal are nodes 0 and 1
+    let junctions = [start, goal]
+        .into_iter()
+        .chain(grid.positions().filter(|&pos| {
+            grid[pos] != b'#'
+                && grid
+                    .neighbours4(pos)
+                    .filter(|&next| grid[next] != b'#')
+                    .count()
+                    > 2
+        }))
+        .collect::<Vec<_>>();
+    if junctions.len() > 64 {
+        return Err(input.error("maze with at most 64 junctions"));
+    }
+    let nodes = junctions
+        .iter()
+        .enumerate()
+        .map(|(i, &pos)| (pos, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut trails = Trails {
+        downhill: vec![Vec::new(); junctions.len()],
+        any: vec![Vec::new(); junctions.len()],
+    };
+    for (node, &pos) in junctions.iter().enumerate() {
+        for dir in Dir::ALL {
+            if let Some((target, len, downhill)) = follow(&grid, &nodes, pos, dir) {
+                trails.any[node].push((target, len));
+                if downhill {
+                    trails.downhill[node].push((target, len));
+                }
+            }
+        }
+    }
+
+    Ok(trails)
+}
+
+/// Follows the trail leaving the junction at `pos` in the direction `dir` to
+/// the next junction. Returns the node of that junction, the length of the
+/// trail, and whether the trail can be taken without climbing any slopes.
+fn follow(
+    grid: &Grid<u8>,
+    nodes: &HashMap<Pos, usize>,
+    start: Pos,
+    dir: Dir,
+) -> Option<(usize, u32, bool)> {
+    let mut prev = start;
+    let mut pos = grid.step(start, dir).filter(|&pos| grid[pos] != b'#')?;
+    let mut len = 1;
+    let mut downhill = is_downhill(grid[pos], dir);
+
+    loop {
+        if let Some(&node) = nodes.get(&pos) {
+            return (node != nodes[&start]).then_some((node, len, downhill));
+        }
+
+        // anything but a junction has at most one way on, or is a dead end
+        let (dir, next) = Dir::ALL.into_iter().find_map(|dir| {
+            grid.step(pos, dir)
+                .filter(|&next| next != prev && grid[next] != b'#')
+                .map(|next| (dir, next))
+        })?;
+
+        downhill &= is_downhill(grid[next], dir);
+        prev = pos;
+        pos = next;
+        len += 1;
+    }
+}
+
+/// Returns whether a tile can be stepped onto in the direction `dir`.
+fn is_downhill(tile: u8, dir: Dir) -> bool {
+    match tile {
+        b'^' => dir == Dir::Up,
+        b'>' => dir == Dir::Right,
+        b'v' => dir == Dir::Down,
+        b'<' => dir == Dir::Left,
+        _ => true,
+    }
+}
+
+#[aoc(day23, part1)]
+fn part1(trails: &Trails) -> Option<u32> {
+    longest_hike(&trails.downhill)
+}
+
+#[aoc(day23, part2)]
+fn part2(trails: &Trails) -> Option<u32> {
+    longest_hike(&trails.any)
+}
+
+/// A graph of the junctions in the maze, including the start (node 0) and
+/// the goal (node 1), with the lengths of the trails between them.
+#[derive(Clone, Debug)]
+struct Trails {
+    /// Trails that don't climb any slopes.
+    downhill: Vec<Vec<(usize, u32)>>,
+    /// All trails, in both directions.
+    any: Vec<Vec<(usize, u32)>>,
+}
+
+const START: usize = 0;
+const GOAL: usize = 1;
+
+/// Returns the length of the longest path from the start to the goal that
+/// doesn't visit any junction twice.
+fn longest_hike(edges: &[Vec<(usize, u32)>]) -> Option<u32> {
+    struct Search<'a> {
+        edges: &'a [Vec<(usize, u32)>],
+        /// The longest trail leading to each node.
+        max_in: Vec<u32>,
+        /// The only node with a trail to the goal, if there is just one.
+        last: Option<usize>,
+        best: Option<u32>,
+    }
+
+    impl Search<'_> {
+        /// `remaining` is the sum of `max_in` over all unvisited nodes, which
+        /// bounds how much longer the path can get.
+        fn visit(&mut self, node: usize, visited: u64, len: u32, remaining: u32) {
+            if node == GOAL {
+                self.best = self.best.max(Some(len));
+                return;
+            }
+
+            for &(next, edge_len) in &self.edges[node] {
+                if visited & 1 << next != 0 {
+                    continue;
+                }
+                // any other way leaves the goal unreachable
+                if Some(node) == self.last && next != GOAL {
+                    continue;
+                }
+
+                let remaining = remaining - self.max_in[next];
+                if self
+                    .best
+                    .is_some_and(|best| len + edge_len + remaining <= best)
+                {
+                    continue;
+                }
+
+                self.visit(next, visited | 1 << next, len + edge_len, remaining);
+            }
+        }
+    }
+
+    let mut max_in = vec![0; edges.len()];
+    for &(target, len) in edges.iter().flatten() {
+        max_in[target] = max_in[target].max(len);
+    }
+
+    let into_goal = (0..edges.len())
+        .filter(|&node| edges[node].iter().any(|&(target, _)| target == GOAL))
+        .collect::<Vec<_>>();
+    let last = match into_goal[..] {
+        [last] => Some(last),
+        _ => None,
+    };
+
+    let remaining = max_in.iter().sum::<u32>() - max_in[START];
+    let mut search = Search {
+        edges,
+        max_in,
+        last,
+        best: None,
+    };
+    search.visit(START, 1 << START, 0, remaining);
+    search.best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), Some(94));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), Some(154));
+    }
+}
@@ -0,0 +1,222 @@
+use std::ops::RangeInclusive;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    num::{gcd, Rational},
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day24)]
+fn parse_input(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    Cursor::new(24, input)
+        .lines()
+        .map(|line| {
+            let (pos, vel) = line.split_once("@")?;
+            Ok(Hailstone {
+                pos: vector(pos)?,
+                vel: vector(vel)?,
+            })
+        })
+        .collect()
+}
+
+/// Parses three comma-separated coordinates.
+fn vector(input: Cursor) -> Result<Vec3, ParseError> {
+    let mut parts = input.trim().delimited(",");
+    let mut vector = [0; 3];
+    for coord in &mut vector {
+        let mut part = parts
+            .next()
+            .ok_or_else(|| input.error_at_end("three coordinates"))?;
+        *coord = part.int()?;
+        part.end()?;
+    }
+    if let Some(part) = parts.next() {
+        return Err(part.error("three coordinates"));
+    }
+
+    Ok(vector)
+}
+
+const TEST_AREA: RangeInclusive<i128> = 200_000_000_000_000..=400_000_000_000_000;
+
+#[aoc(day24, part1)]
+fn part1(hail: &[Hailstone]) -> usize {
+    count_crossings(hail, TEST_AREA)
+}
+
+#[aoc(day24, part2)]
+fn part2(hail: &[Hailstone]) -> Option<i128> {
+    let (pos, _) = hail.windows(3).find_map(|w| {
+        throw(&w[0], &w[1], &w[2]).filter(|&(pos, vel)| hail.iter().all(|h| h.is_hit_by(pos, vel)))
+    })?;
+
+    Some(pos.iter().sum())
+}
+
+/// Counts the pairs of hailstones whose future paths cross within `area` in
+/// both x and y, ignoring z.
+fn count_crossings(hail: &[Hailstone], area: RangeInclusive<i128>) -> usize {
+    let area = Rational::from(*area.start())..=Rational::from(*area.end());
+
+    let mut count = 0;
+    for (i, a) in hail.iter().enumerate() {
+        for b in &hail[i + 1..] {
+            // a.pos + t * a.vel = b.pos + s * b.vel, solved by Cramer's rule.
+            // Parallel paths never cross, as no two hailstones in the input
+            // share a path.
+            let det = cross2(a.vel, b.vel);
+            if det == 0 {
+                continue;
+            }
+
+            let diff = sub(b.pos, a.pos);
+            let t = Rational::new(cross2(diff, b.vel), det);
+            let s = Rational::new(cross2(diff, a.vel), det);
+            if t < Rational::ZERO || s < Rational::ZERO {
+                continue;
+            }
+
+            let x = Rational::from(a.pos[0]) + t * Rational::from(a.vel[0]);
+            let y = Rational::from(a.pos[1]) + t * Rational::from(a.vel[1]);
+            if area.contains(&x) && area.contains(&y) {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Finds the position and velocity of a throw that hits the hailstones `a`,
+/// `b` and `c`, if there is exactly one and it is integral.
+fn throw(a: &Hailstone, b: &Hailstone, c: &Hailstone) -> Option<(Vec3, Vec3)> {
+    // In the frame of `a`, the rock passes through the origin, so its path
+    // lies in the plane through the origin and the path of `b`. The rock hits
+    // `c` where `c` crosses that plane, and likewise for `b`.
+    let (q1, w1) = (sub(b.pos, a.pos), sub(b.vel, a.vel));
+    let (q2, w2) = (sub(c.pos, a.pos), sub(c.vel, a.vel));
+    let n1 = reduce(cross(q1, w1))?;
+    let n2 = reduce(cross(q2, w2))?;
+
+    let time = |q, w, n| {
+        let denom = dot(w, n);
+        (denom != 0).then(|| Rational::new(-dot(q, n), denom))
+    };
+    let t1 = time(q1, w1, n2)?;
+    let t2 = time(q2, w2, n1)?;
+    if t1 == t2 {
+        return None;
+    }
+
+    let hit1 = b.at(t1);
+    let hit2 = c.at(t2);
+    let mut pos = [0; 3];
+    let mut vel = [0; 3];
+    for i in 0..3 {
+        let v = (hit2[i] - hit1[i]) / (t2 - t1);
+        vel[i] = v.to_integer()?;
+        pos[i] = (hit1[i] - t1 * v).to_integer()?;
+    }
+
+    Some((pos, vel))
+}
+
+type Vec3 = [i128; 3];
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Vec3, b: Vec3) -> i128 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// The z component of the cross product of the xy parts of `a` and `b`.
+fn cross2(a: Vec3, b: Vec3) -> i128 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+/// Divides `a` by the gcd of its components, which keeps later products
+/// within `i128`. Returns `None` for the zero vector.
+fn reduce(a: Vec3) -> Option<Vec3> {
+    let g = gcd(gcd(a[0], a[1]), a[2]).abs();
+    (g != 0).then(|| a.map(|x| x / g))
+}
+
+#[derive(Clone, Debug)]
+struct Hailstone {
+    pos: Vec3,
+    vel: Vec3,
+}
+
+impl Hailstone {
+    /// Returns the position of the hailstone at time `t`.
+    fn at(&self, t: Rational) -> [Rational; 3] {
+        [0, 1, 2].map(|i| Rational::from(self.pos[i]) + t * Rational::from(self.vel[i]))
+    }
+
+    /// Returns whether a rock thrown from `pos` with velocity `vel` hits the
+    /// hailstone at some time `t >= 0`.
+    fn is_hit_by(&self, pos: Vec3, vel: Vec3) -> bool {
+        // the rock closes the gap iff it moves towards the hailstone along
+        // the line between them
+        let gap = sub(self.pos, pos);
+        let closing = sub(vel, self.vel);
+        gap == [0; 3] || (cross(gap, closing) == [0; 3] && dot(gap, closing) > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(count_crossings(&parse_input(EXAMPLE).unwrap(), 7..=27), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), Some(47));
+    }
+
+    #[test]
+    fn throw_example() {
+        let hail = parse_input(EXAMPLE).unwrap();
+        assert_eq!(
+            throw(&hail[0], &hail[1], &hail[2]),
+            Some(([24, 13, 10], [-3, 1, 2]))
+        );
+    }
+
+    #[test]
+    fn large_coordinates() {
+        // the paths cross at (2 * 10^14 + 1/3, 3 * 10^14), just past the edge
+        // of the first area
+        let hail = parse_input(
+            "\
+200000000000000, 300000000000000, 0 @ 1, 0, 0
+200000000000001, 299999999999999, 0 @ -2, 3, 0",
+        )
+        .unwrap();
+        assert_eq!(count_crossings(&hail, 0..=200_000_000_000_000), 0);
+        assert_eq!(count_crossings(&hail, 0..=300_000_000_000_000), 1);
+    }
+}
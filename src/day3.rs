@@ -1,44 +1,41 @@
+use std::collections::HashMap;
+
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::utils::{
+    grid::{Grid, Pos},
+    parse::{Cursor, ParseError},
+};
+
 #[aoc_generator(day3)]
-fn parse_input(input: &str) -> Schematic {
-    let mut schematic = Schematic::default();
+fn parse_input(input: &str) -> Result<Schematic, ParseError> {
+    let input = Cursor::new(3, input);
+    let grid = Grid::parse(input)?;
+    let mut numbers = Vec::new();
 
-    for (i, mut line) in input.lines().enumerate() {
+    for ((i, row), line) in grid.rows().enumerate().zip(input.lines()) {
         let mut j = 0;
-        while !line.is_empty() {
-            if line.starts_with(|ch: char| ch.is_ascii_digit()) {
-                let (num, rest) = line.split_at(
-                    line.find(|ch: char| !ch.is_ascii_digit())
-                        .unwrap_or(line.len()),
-                );
-                schematic.numbers.push(Number {
-                    i,
-                    j,
-                    l: num.len(),
-                    n: num.parse::<u32>().unwrap(),
-                });
-                line = rest;
-                j += num.len();
-            } else if line.starts_with(|ch: char| ch != '.') {
-                let mut c = line.chars();
-                let s = c.next().unwrap();
-                schematic.symbols.push(Symbol { i, j, s });
-                line = c.as_str();
+        while j < row.len() {
+            let len = row[j..].iter().take_while(|b| b.is_ascii_digit()).count();
+            if len == 0 {
                 j += 1;
+                continue;
             }
 
-            match line.find(|ch: char| ch != '.') {
-                Some(index) => {
-                    line = &line[index..];
-                    j += index;
-                }
-                None => break,
-            }
+            // digits are ASCII, so the column is also the byte offset
+            let mut digits = line;
+            digits.field(j)?;
+            let n = digits.int()?;
+            numbers.push(Number {
+                pos: Pos::new(i, j),
+                len,
+                n,
+            });
+            j += len;
         }
     }
 
-    schematic
+    Ok(Schematic { grid, numbers })
 }
 
 #[aoc(day3, part1)]
@@ -46,57 +43,98 @@ fn part1(input: &Schematic) -> u32 {
     input
         .numbers
         .iter()
-        .filter(|num| input.symbols.iter().any(|sym| num.is_adjacent_to(sym)))
+        .filter(|num| input.adjacent_symbols(num).next().is_some())
         .map(|num| num.n)
         .sum()
 }
 
 #[aoc(day3, part2)]
 fn part2(input: &Schematic) -> u32 {
-    input
-        .symbols
-        .iter()
-        .filter(|sym| sym.s == '*')
-        .filter_map(|sym| {
-            let mut part_numbers = input.numbers.iter().filter(|num| num.is_adjacent_to(sym));
-
-            let pn1 = part_numbers.next()?.n;
-            let pn2 = part_numbers.next()?.n;
-            if part_numbers.next().is_some() {
-                return None;
+    let mut gears = HashMap::<Pos, Vec<u32>>::new();
+    for num in &input.numbers {
+        for pos in input.adjacent_symbols(num) {
+            if input.grid[pos] == b'*' {
+                gears.entry(pos).or_default().push(num.n);
             }
+        }
+    }
 
-            Some(pn1 * pn2)
+    gears
+        .values()
+        .filter_map(|part_numbers| match part_numbers[..] {
+            [pn1, pn2] => Some(pn1 * pn2),
+            _ => None,
         })
         .sum()
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 struct Schematic {
+    grid: Grid<u8>,
     numbers: Vec<Number>,
-    symbols: Vec<Symbol>,
+}
+
+impl Schematic {
+    /// Returns the positions of all symbols adjacent to a number, including
+    /// diagonally.
+    fn adjacent_symbols<'a>(&'a self, num: &Number) -> impl Iterator<Item = Pos> + 'a {
+        let Pos { row, col } = num.pos;
+
+        self.grid
+            .region(
+                row.saturating_sub(1)..row + 2,
+                col.saturating_sub(1)..col + num.len + 1,
+            )
+            .filter(|&pos| is_symbol(self.grid[pos]))
+    }
 }
 
 #[derive(Clone, Debug)]
 struct Number {
-    i: usize,
-    j: usize,
-    l: usize,
+    pos: Pos,
+    len: usize,
     n: u32,
 }
 
-impl Number {
-    fn is_adjacent_to(&self, symbol: &Symbol) -> bool {
-        symbol.i + 1 >= self.i
-            && symbol.i <= self.i + 1
-            && symbol.j + 1 >= self.j
-            && symbol.j <= self.j + self.l
-    }
+fn is_symbol(b: u8) -> bool {
+    b != b'.' && !b.is_ascii_digit()
 }
 
-#[derive(Clone, Debug)]
-struct Symbol {
-    i: usize,
-    j: usize,
-    s: char,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 4361);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 467835);
+    }
+
+    #[test]
+    fn truncated_line() {
+        let err = parse_input("467..\n..*").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+    }
+
+    #[test]
+    fn number_too_large() {
+        let err = parse_input("..............\n..99999999999*").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
 }
@@ -2,23 +2,24 @@ use std::collections::{BTreeSet, VecDeque};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::utils::parse::{Cursor, ParseError};
+
 #[aoc_generator(day4)]
-fn parse_input(input: &str) -> Vec<Card> {
-    input
+fn parse_input(input: &str) -> Result<Vec<Card>, ParseError> {
+    Cursor::new(4, input)
         .lines()
         .map(|line| {
-            let (winning, ticket) = line.split_once(':').unwrap().1.split_once('|').unwrap();
-
-            let winning = winning
-                .split_whitespace()
-                .map(|n| n.trim().parse().unwrap())
-                .collect();
-            let ticket = ticket
-                .split_whitespace()
-                .map(|n| n.trim().parse().unwrap())
-                .collect();
-
-            Card { winning, ticket }
+            let (mut card, numbers) = line.key_value()?;
+            card.tag("Card")?;
+            card.skip_whitespace();
+            card.int::<u32>()?;
+            card.end()?;
+
+            let (winning, ticket) = numbers.split_once("|")?;
+            let winning = winning.ints().collect::<Result<_, _>>()?;
+            let ticket = ticket.ints().collect::<Result<_, _>>()?;
+
+            Ok(Card { winning, ticket })
         })
         .collect()
 }
@@ -52,3 +53,34 @@ struct Card {
     winning: BTreeSet<u32>,
     ticket: BTreeSet<u32>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 30);
+    }
+
+    #[test]
+    fn truncated_line() {
+        let err = parse_input("Card 1: 41 48 | 83 86\nCard 2: 13 32")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column), (2, 14));
+    }
+}
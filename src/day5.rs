@@ -0,0 +1,223 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::utils::{
+    interval::{Interval, IntervalSet},
+    parse::{Cursor, ParseError},
+};
+
+#[aoc_generator(day5)]
+fn parse_input(input: &str) -> Result<Almanac, ParseError> {
+    let input = Cursor::new(5, input);
+    let mut blocks = input.blocks();
+
+    let (mut key, seeds) = blocks
+        .next()
+        .ok_or_else(|| input.error("`seeds:`"))?
+        .key_value()?;
+    key.tag("seeds")?;
+    key.end()?;
+    let seeds_line = seeds;
+    let seeds = seeds.ints().collect::<Result<Vec<_>, _>>()?;
+    // part 2 reads the seeds as pairs of range starts and lengths
+    if seeds.is_empty() || seeds.len() % 2 != 0 {
+        return Err(seeds_line.error_at_end("an even, non-zero number of seeds"));
+    }
+
+    let mut maps: Vec<Map> = Vec::new();
+    for block in blocks {
+        let mut lines = block.lines();
+
+        let mut header = lines.next().ok_or_else(|| block.error("map header"))?;
+        let from = header.take_until("-to-")?;
+        let to = header.take_until(" map:")?;
+        header.end()?;
+
+        // input check: maps form a single chain from seeds to locations
+        let expected_from = maps.last().map_or("seed", |map| &map.to);
+        if from.as_str() != expected_from {
+            return Err(from.error(format!("map from `{expected_from}`")));
+        }
+
+        let mut ranges = lines
+            .map(|line| {
+                let mut numbers = line.ints();
+                let mut next = || {
+                    numbers
+                        .next()
+                        .unwrap_or_else(|| Err(line.error_at_end("integer")))
+                };
+
+                let range = MapRange {
+                    dst: next()?,
+                    src: next()?,
+                    len: next()?,
+                };
+                match numbers.next() {
+                    Some(_) => Err(line.error("exactly three integers")),
+                    None => Ok(range),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        ranges.sort_by_key(|range| range.src);
+
+        maps.push(Map {
+            to: to.as_str().to_string(),
+            ranges,
+        });
+    }
+
+    if maps.last().map(|map| map.to.as_str()) != Some("location") {
+        return Err(input.error_at_end("map to `location`"));
+    }
+
+    Ok(Almanac { seeds, maps })
+}
+
+#[aoc(day5, part1)]
+fn part1(input: &Almanac) -> u64 {
+    input
+        .seeds
+        .iter()
+        .map(|&seed| input.maps.iter().fold(seed, |n, map| map.map(n)))
+        .min()
+        // the generator checks that there is at least one seed
+        .unwrap()
+}
+
+#[aoc(day5, part2)]
+fn part2(input: &Almanac) -> Option<u64> {
+    let seeds = input
+        .seeds
+        .chunks(2)
+        .map(|pair| Interval::with_len(pair[0], pair[1]))
+        .collect::<IntervalSet<_>>();
+
+    input
+        .maps
+        .iter()
+        .fold(seeds, |set, map| map.map_set(&set))
+        .min()
+}
+
+#[derive(Clone, Debug)]
+struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<Map>,
+}
+
+#[derive(Clone, Debug)]
+struct Map {
+    to: String,
+    /// Map ranges, sorted by source start.
+    ranges: Vec<MapRange>,
+}
+
+impl Map {
+    fn map(&self, n: u64) -> u64 {
+        self.ranges
+            .iter()
+            .find(|range| range.src_interval().contains(n))
+            .map(|range| n - range.src + range.dst)
+            .unwrap_or(n)
+    }
+
+    /// Maps a set of integers through this map, splitting it at the
+    /// boundaries of the map ranges.
+    fn map_set(&self, set: &IntervalSet<u64>) -> IntervalSet<u64> {
+        let mut output = IntervalSet::new();
+        let mut rest = set.clone();
+
+        for range in &self.ranges {
+            let (inside, outside) = rest.split_by(&range.src_interval());
+            output = output.union(&inside.translate(range.src, range.dst));
+            rest = outside;
+        }
+
+        // integers outside every map range are left unchanged
+        output.union(&rest)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct MapRange {
+    dst: u64,
+    src: u64,
+    len: u64,
+}
+
+impl MapRange {
+    fn src_interval(&self) -> Interval<u64> {
+        Interval::with_len(self.src, self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 35);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), Some(46));
+    }
+
+    #[test]
+    fn odd_number_of_seeds() {
+        let err = parse_input("seeds: 79 14 55\n\nseed-to-location map:\n50 98 2").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 16));
+
+        let err = parse_input("seeds:\n\nseed-to-location map:\n50 98 2").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
+    }
+
+    #[test]
+    fn truncated_map_line() {
+        let err = parse_input("seeds: 79 14\n\nseed-to-location map:\n50 98 2\n52 50").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 6));
+    }
+
+    #[test]
+    fn empty_seed_ranges() {
+        let almanac = parse_input("seeds: 79 0\n\nseed-to-location map:\n50 98 2").unwrap();
+        assert_eq!(part2(&almanac), None);
+    }
+}
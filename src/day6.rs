@@ -2,35 +2,44 @@ use std::fmt::Write as _;
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::utils::binary_search::binary_search_range;
+use crate::utils::{
+    binary_search::binary_search_range,
+    num::isqrt,
+    parse::{Cursor, ParseError},
+};
 
 #[aoc_generator(day6)]
-fn parse_input(input: &str) -> Vec<Race> {
+fn parse_input(input: &str) -> Result<Vec<Race>, ParseError> {
+    let input = Cursor::new(6, input);
     let mut lines = input.lines();
 
-    let mut times = lines
-        .next()
-        .unwrap()
-        .strip_prefix("Time: ")
-        .unwrap()
-        .split_whitespace()
-        .map(str::trim);
-    let mut distances = lines
-        .next()
-        .unwrap()
-        .strip_prefix("Distance: ")
-        .unwrap()
-        .split_whitespace()
-        .map(str::trim);
-
-    times
-        .by_ref()
-        .zip(&mut distances)
-        .map(|(time, dist)| Race {
-            time: time.parse().unwrap(),
-            dist: dist.parse().unwrap(),
-        })
-        .collect()
+    let mut numbers = |name| {
+        let line = lines
+            .next()
+            .ok_or_else(|| input.error_at_end(format!("`{name}:`")))?;
+        let (mut key, numbers) = line.key_value()?;
+        key.tag(name)?;
+        key.end()?;
+
+        let numbers = numbers.ints().collect::<Result<Vec<u64>, _>>()?;
+        if numbers.is_empty() {
+            return Err(line.error_at_end("integer"));
+        }
+        Ok((line, numbers))
+    };
+
+    let (_, times) = numbers("Time")?;
+    let (dist_line, distances) = numbers("Distance")?;
+
+    if times.len() != distances.len() {
+        return Err(dist_line.error_at_end("as many distances as times"));
+    }
+
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, dist)| Race { time, dist })
+        .collect())
 }
 
 #[aoc(day6, part1, direct_search)]
@@ -48,6 +57,7 @@ fn part1_binary_search(input: &[Race]) -> u64 {
         .map(|race| race.count_ways_to_win_binary_search())
         .product()
 }
+
 #[aoc(day6, part1, solve_quadratic)]
 fn part1_solve_quadratic(input: &[Race]) -> u64 {
     input
@@ -56,21 +66,37 @@ fn part1_solve_quadratic(input: &[Race]) -> u64 {
         .product()
 }
 
+#[aoc(day6, part1, solve_quadratic_exact)]
+fn part1_solve_quadratic_exact(input: &[Race]) -> u64 {
+    input
+        .iter()
+        .map(|race| race.count_ways_to_win_solve_quadratic_exact())
+        .product()
+}
+
 #[aoc(day6, part2, direct_search)]
-fn part2_direct_search(input: &[Race]) -> u64 {
-    parse_concat_race(input).count_ways_to_win_direct_search()
+fn part2_direct_search(input: &[Race]) -> Option<u64> {
+    parse_concat_race(input).map(|race| race.count_ways_to_win_direct_search())
 }
 
 #[aoc(day6, part2, binary_search)]
-fn part2_binary_search(input: &[Race]) -> u64 {
-    parse_concat_race(input).count_ways_to_win_binary_search()
+fn part2_binary_search(input: &[Race]) -> Option<u64> {
+    parse_concat_race(input).map(|race| race.count_ways_to_win_binary_search())
 }
+
 #[aoc(day6, part2, solve_quadratic)]
-fn part2_solve_quadratic(input: &[Race]) -> u64 {
-    parse_concat_race(input).count_ways_to_win_solve_quadratic()
+fn part2_solve_quadratic(input: &[Race]) -> Option<u64> {
+    parse_concat_race(input).map(|race| race.count_ways_to_win_solve_quadratic())
 }
 
-fn parse_concat_race(races: &[Race]) -> Race {
+#[aoc(day6, part2, solve_quadratic_exact)]
+fn part2_solve_quadratic_exact(input: &[Race]) -> Option<u64> {
+    parse_concat_race(input).map(|race| race.count_ways_to_win_solve_quadratic_exact())
+}
+
+/// Concatenates the digits of all races into a single race, or returns `None`
+/// if the result doesn't fit in a `u64`.
+fn parse_concat_race(races: &[Race]) -> Option<Race> {
     let (time, dist) = races.iter().fold(
         (String::new(), String::new()),
         |(mut time, mut dist), race| {
@@ -80,10 +106,10 @@ fn parse_concat_race(races: &[Race]) -> Race {
         },
     );
 
-    Race {
-        time: time.parse().unwrap(),
-        dist: dist.parse().unwrap(),
-    }
+    Some(Race {
+        time: time.parse().ok()?,
+        dist: dist.parse().ok()?,
+    })
 }
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -100,9 +126,9 @@ impl Race {
 
         let mut range = 1..self.time;
 
-        let first = range
-            .find(|t| p(*t) > self.dist)
-            .unwrap_or_else(|| panic!("race has no possible time to beat record (race={self:?})"));
+        let Some(first) = range.find(|t| p(*t) > self.dist) else {
+            return 0;
+        };
 
         // Take advantage of the fact that p(t) is symmetric about t = self.time / 2
         let last = self.time - first;
@@ -117,10 +143,15 @@ impl Race {
 
         // Take advantage of the fact that p(t) is symmetric about t = self.time / 2,
         // we can binary search for the time at which p(t) is positive.
-        let i = binary_search_range(0, (self.time + 1) / 2, |&t| p(t).cmp(&self.dist))
+        let i = binary_search_range(0, self.time.div_ceil(2), |&t| p(t).cmp(&self.dist))
             .unwrap_or_else(|i| i)
             + 1;
 
+        // if the record can't be beaten, the search ends up at the midpoint
+        if i > self.time / 2 || p(i) <= self.dist {
+            return 0;
+        }
+
         self.time - 2 * i + 1
     }
 
@@ -136,10 +167,157 @@ impl Race {
         //     = (self.time / 2) +- sqrt((self.time / 2)**2 - d)
 
         let mt = self.time as f64 / 2.0;
-        let root = (mt * mt - self.dist as f64).sqrt();
+        let root = (mt * mt - self.dist as f64).max(0.0).sqrt();
+
+        let mut first: u64 = (mt - root + 1.0).floor() as u64;
+
+        // correct for floating-point error, which becomes significant once
+        // self.time**2 no longer fits in the mantissa of an f64
+        let p = |t| (self.time - t) * t;
+        while first > 0 && p(first - 1) > self.dist {
+            first -= 1;
+        }
+        while first <= self.time / 2 && p(first) <= self.dist {
+            first += 1;
+        }
 
-        let first: u64 = (mt - root + 1.0).floor() as u64;
+        if first > self.time / 2 {
+            return 0;
+        }
 
         self.time - 2 * first + 1
     }
+
+    fn count_ways_to_win_solve_quadratic_exact(&self) -> u64 {
+        // t beats the record iff
+        //   (self.time - t) * t > d
+        //   <=> (2t - self.time)**2 < self.time**2 - 4d
+        // so we count the integers u = 2t - self.time, which have the same
+        // parity as self.time, with |u| < sqrt(self.time**2 - 4d).
+
+        let time = self.time as u128;
+        let Some(disc) = (time * time).checked_sub(4 * self.dist as u128) else {
+            return 0;
+        };
+
+        // largest |u| with u**2 < disc; a perfect square discriminant means
+        // that its root only ties the record
+        let root = isqrt(disc);
+        let max_u = if root * root == disc {
+            root.checked_sub(1)
+        } else {
+            Some(root)
+        };
+
+        // round down to the parity of self.time
+        let max_u = max_u.and_then(|max_u| {
+            if max_u % 2 == time % 2 {
+                Some(max_u)
+            } else {
+                max_u.checked_sub(1)
+            }
+        });
+
+        // u is one of -max_u, -max_u + 2, ..., max_u
+        max_u.map_or(0, |max_u| max_u as u64 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn part1_example() {
+        let races = parse_input(EXAMPLE).unwrap();
+        assert_eq!(part1_direct_search(&races), 288);
+        assert_eq!(part1_binary_search(&races), 288);
+        assert_eq!(part1_solve_quadratic(&races), 288);
+        assert_eq!(part1_solve_quadratic_exact(&races), 288);
+    }
+
+    #[test]
+    fn part2_example() {
+        let races = parse_input(EXAMPLE).unwrap();
+        assert_eq!(part2_direct_search(&races), Some(71503));
+        assert_eq!(part2_binary_search(&races), Some(71503));
+        assert_eq!(part2_solve_quadratic(&races), Some(71503));
+        assert_eq!(part2_solve_quadratic_exact(&races), Some(71503));
+    }
+
+    #[test]
+    fn missing_numbers() {
+        let err = parse_input("Time:      7  15\nDistance:  9").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 13));
+
+        let err = parse_input("Time:\nDistance:").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+    }
+
+    #[test]
+    fn concatenated_race_too_long() {
+        let races = parse_input("Time: 1000000000 1000000000 1\nDistance: 1 1 1").unwrap();
+        assert_eq!(part2_binary_search(&races), None);
+    }
+
+    fn count_ways_to_win_all(race: Race) -> [u64; 4] {
+        [
+            race.count_ways_to_win_direct_search(),
+            race.count_ways_to_win_binary_search(),
+            race.count_ways_to_win_solve_quadratic(),
+            race.count_ways_to_win_solve_quadratic_exact(),
+        ]
+    }
+
+    #[test]
+    fn tied_record() {
+        // holding the button for 3 or 7 seconds only ties the record
+        assert_eq!(count_ways_to_win_all(Race { time: 10, dist: 21 }), [3; 4]);
+        // holding the button for 5 seconds only ties the record
+        assert_eq!(count_ways_to_win_all(Race { time: 10, dist: 25 }), [0; 4]);
+    }
+
+    #[test]
+    fn no_way_to_win() {
+        assert_eq!(count_ways_to_win_all(Race { time: 0, dist: 0 }), [0; 4]);
+        assert_eq!(count_ways_to_win_all(Race { time: 1, dist: 0 }), [0; 4]);
+        assert_eq!(count_ways_to_win_all(Race { time: 7, dist: 12 }), [0; 4]);
+        assert_eq!(count_ways_to_win_all(Race { time: 7, dist: 100 }), [0; 4]);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn large_races(time in 0..u32::MAX as u64, dist_fraction in 0.0..1.1f64) {
+            // the direct search is too slow for races this long
+            let dist = ((time * time / 4) as f64 * dist_fraction) as u64;
+            let race = Race { time, dist };
+            let counts = [
+                race.count_ways_to_win_binary_search(),
+                race.count_ways_to_win_solve_quadratic(),
+                race.count_ways_to_win_solve_quadratic_exact(),
+            ];
+            proptest::prop_assert!(counts.iter().all(|&count| count == counts[0]), "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn large_race() {
+        // self.time**2 is far beyond the precision of an f64, which puts the
+        // roots of the quadratic off by one
+        let time = 3_080_023_021;
+        let first = 1_498_194_317;
+        let race = Race {
+            time,
+            dist: (time - first) * first,
+        };
+        let expected = time - 2 * (first + 1) + 1;
+
+        assert_eq!(race.count_ways_to_win_binary_search(), expected);
+        assert_eq!(race.count_ways_to_win_solve_quadratic(), expected);
+        assert_eq!(race.count_ways_to_win_solve_quadratic_exact(), expected);
+    }
 }
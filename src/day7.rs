@@ -1,14 +1,20 @@
 use std::str::FromStr;
 
 use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::Itertools;
+
+use crate::utils::parse::{Cursor, ParseError};
 
 #[aoc_generator(day7)]
-fn parse_input(s: &str) -> Vec<(Hand, usize)> {
-    s.lines()
-        .map(|line| {
-            let (hand, bid) = line.split_whitespace().collect_tuple().unwrap();
-            (hand.parse().unwrap(), bid.parse().unwrap())
+fn parse_input(input: &str) -> Result<Vec<(Hand, usize)>, ParseError> {
+    Cursor::new(7, input)
+        .lines()
+        .map(|mut line| {
+            let hand = line.word()?;
+            let hand = hand.as_str().parse().map_err(|e| hand.error(e))?;
+            let bid = line.int()?;
+            line.end()?;
+
+            Ok((hand, bid))
         })
         .collect()
 }
@@ -47,15 +53,15 @@ impl Card {
     const JACK: Card = Card(11);
     const JOKER: Card = Card(0);
 
-    fn from_char(ch: char) -> Self {
+    fn from_char(ch: char) -> Option<Self> {
         match ch {
-            '2'..='9' => Self(ch.to_digit(10).unwrap() as u8),
-            'T' => Self(10),
-            'J' => Self(11),
-            'Q' => Self(12),
-            'K' => Self(13),
-            'A' => Self(14),
-            _ => panic!("invalid card rank: {ch:?}"),
+            '2'..='9' => Some(Self(ch as u8 - b'0')),
+            'T' => Some(Self(10)),
+            'J' => Some(Self(11)),
+            'Q' => Some(Self(12)),
+            'K' => Some(Self(13)),
+            'A' => Some(Self(14)),
+            _ => None,
         }
     }
 }
@@ -82,7 +88,7 @@ impl Rank {
         }
         counts.sort();
 
-        let has_jokers = cards.iter().any(|&card| card == Card::JOKER);
+        let has_jokers = cards.contains(&Card::JOKER);
 
         match (counts, has_jokers) {
             ([_, _, _, _, 5], _) => Self::FiveOfAKind,
@@ -128,14 +134,43 @@ impl FromStr for Hand {
         let mut cards = [Card(0); 5];
         let mut chars = s.chars();
         for card in cards.iter_mut() {
-            *card = Card::from_char(chars.next().ok_or("Not enough cards")?);
+            let ch = chars.next().ok_or("five cards")?;
+            *card = Card::from_char(ch).ok_or("card rank (`2`-`9`, `T`, `J`, `Q`, `K` or `A`)")?;
         }
         if chars.next().is_none() {
             let rank = Rank::from_cards(cards);
 
             Ok(Self { rank, cards })
         } else {
-            Err("Too many cards")
+            Err("five cards")
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 6440);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 5905);
+    }
+
+    #[test]
+    fn truncated_line() {
+        let err = parse_input("32T3K 765\nT55J5").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 6));
+    }
+}
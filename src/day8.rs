@@ -6,118 +6,220 @@ use std::{
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::utils::num::lcm;
+use crate::utils::{
+    num::crt,
+    parse::{Cursor, ParseError},
+};
 
 use self::Dir::{L, R};
 
 #[aoc_generator(day8)]
-fn parse_input(s: &str) -> Map {
-    let mut lines = s.lines();
+fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let input = Cursor::new(8, input);
+    let mut blocks = input.blocks();
 
-    let instrs = lines
-        .next()
-        .unwrap()
+    let mut instrs_line = blocks.next().ok_or_else(|| input.error("instructions"))?;
+    let instrs = instrs_line
+        .take_while(|ch| matches!(ch, 'L' | 'R'))
+        .as_str()
         .chars()
-        .map(|ch| match ch {
-            'R' => R,
-            'L' => L,
-            _ => panic!("unknown instruction: {ch:?}"),
-        })
+        .map(|ch| if ch == 'R' { R } else { L })
         .collect();
+    if !instrs_line.is_empty() {
+        return Err(instrs_line.error("`L` or `R`"));
+    }
 
-    assert_eq!(lines.next().unwrap(), "");
-
-    let node_map = lines
+    let node_name = "three-character node name";
+    let nodes = blocks
+        .next()
+        .ok_or_else(|| input.error_at_end("node map"))?
+        .lines()
         .map(|line| {
-            let (node, children) = line.split_once('=').unwrap();
-            let node = node.trim().parse().unwrap();
-
-            let (left, right) = children
-                .trim()
-                .strip_prefix('(')
-                .unwrap()
-                .strip_suffix(')')
-                .unwrap()
-                .split_once(',')
-                .unwrap();
-
-            let left = left.trim().parse().unwrap();
-            let right = right.trim().parse().unwrap();
-
-            (node, (left, right))
+            let (node, mut rest) = line.split_once(" = ")?;
+            let (left, right) = rest.parens()?.split_once(", ")?;
+            rest.end()?;
+
+            let node = node.parse(node_name)?;
+            let left_node = left.parse(node_name)?;
+            let right_node = right.parse(node_name)?;
+
+            Ok((node, [(left, left_node), (right, right_node)]))
         })
-        .collect();
+        .collect::<Result<Vec<_>, ParseError>>()?;
 
-    Map { instrs, node_map }
+    let node_map = nodes
+        .iter()
+        .map(|&(node, [(_, left), (_, right)])| (node, (left, right)))
+        .collect::<HashMap<_, _>>();
+
+    // input check: every child is defined, so walking the map can't get stuck
+    for (child, node) in nodes.iter().flat_map(|(_, children)| children) {
+        if !node_map.contains_key(node) {
+            return Err(child.error("defined node"));
+        }
+    }
+
+    Ok(Map { instrs, node_map })
 }
 
 #[aoc(day8, part1)]
-fn part1(map: &Map) -> usize {
-    let mut current = Node::START;
-    let mut count = 0;
+fn part1(map: &Map) -> Option<usize> {
+    if !map.node_map.contains_key(&Node::START) {
+        return None;
+    }
 
-    while current != Node::END {
+    // each traversal starts at one of the nodes, so if none of the first
+    // `node_map.len()` traversals ends at `ZZZ`, they repeat without ever
+    // reaching it
+    let mut current = Node::START;
+    for traversals in 0..=map.node_map.len() {
+        if current == Node::END {
+            return Some(traversals * map.instrs.len());
+        }
         current = traverse(current, map);
-        count += map.instrs.len();
     }
 
-    count
+    None
 }
 
 #[aoc(day8, part2)]
-fn part2(map: &Map) -> usize {
-    let start_nodes = map
+fn part2(map: &Map) -> Option<u64> {
+    let ghosts = map
         .node_map
         .keys()
         .copied()
         .filter(|node| node.is_start())
+        .map(|start| EndTimes::find(start, map))
         .collect::<Vec<_>>();
 
-    start_nodes
+    // end times before every ghost has entered its cycle have to be checked
+    // one by one
+    let cycle_start = ghosts.iter().map(|ghost| ghost.cycle_start).max()?;
+    if let Some(t) = ghosts[0]
         .iter()
-        .map(|&start| {
-            // for each start node, compute distance to reach an end node
-            let mut node = start;
-            let mut n = 0;
-            while !node.is_end() {
-                node = traverse(node, map);
-                n += 1;
-            }
-            (node, n)
+        .take_while(|&t| t < cycle_start)
+        .find(|&t| ghosts.iter().all(|ghost| ghost.is_end_at(t)))
+    {
+        return Some(t);
+    }
+
+    // after that, each ghost is at an end node iff the time is congruent to
+    // one of its cycle offsets, so combine these congruences for all ghosts
+    let mut congruences = vec![(0, 1)];
+    for ghost in &ghosts {
+        congruences = congruences
+            .iter()
+            .flat_map(|&(r, m)| {
+                ghost
+                    .cycle
+                    .iter()
+                    .filter_map(move |&offset| crt(r, m, offset as i128, ghost.cycle_len as i128))
+            })
+            .collect();
+        congruences.sort_unstable();
+        congruences.dedup();
+    }
+
+    // find the first time after `cycle_start` matching any of the congruences
+    congruences
+        .into_iter()
+        .map(|(r, m)| {
+            let (r, m) = (r as u64, m as u64);
+            r + cycle_start.saturating_sub(r).div_ceil(m) * m
         })
-        .map(|(end, n)| {
-            // for each end node, compute distance to reach another end node
-            let mut node = traverse(end, map);
-            let mut m = 1;
-            while !node.is_end() {
-                node = traverse(node, map);
-                m += 1;
+        .min()
+}
+
+/// The times at which a ghost is at an end node.
+///
+/// A ghost's state is its node and its position in the instruction list.
+/// Since there are finitely many states, each ghost will eventually enter a
+/// cycle of states.
+#[derive(Clone, Debug)]
+struct EndTimes {
+    /// Times before the ghost enters its cycle at which it is at an end node.
+    prefix: Vec<u64>,
+    /// The time at which the ghost first enters its cycle.
+    cycle_start: u64,
+    /// The length of the cycle.
+    cycle_len: u64,
+    /// Times in `cycle_start..cycle_start + cycle_len` at which the ghost is
+    /// at an end node. The ghost is then at an end node at these times plus
+    /// any multiple of the cycle length.
+    cycle: Vec<u64>,
+}
+
+impl EndTimes {
+    fn find(start: Node, map: &Map) -> Self {
+        let mut seen = HashMap::new();
+        let mut ends = Vec::new();
+
+        let mut node = start;
+        let mut t = 0;
+        let cycle_start = loop {
+            let i = (t % map.instrs.len() as u64) as usize;
+            if let Some(&cycle_start) = seen.get(&(node, i)) {
+                break cycle_start;
             }
+            seen.insert((node, i), t);
 
-            // input check: end nodes are in a loop with no other end nodes
-            assert_eq!(end, node);
-            // input check: end node loop lengths are equal to each starting
-            // node's distance to the end node loop
-            assert_eq!(n, m);
+            if node.is_end() {
+                ends.push(t);
+            }
 
-            (end, n)
-        })
-        .map(|(_, n)| n as usize)
-        .reduce(lcm)
-        .unwrap_or(1)
-        * map.instrs.len()
+            node = step(node, map.instrs[i], map);
+            t += 1;
+        };
+
+        let cycle = ends.split_off(ends.partition_point(|&end| end < cycle_start));
+
+        Self {
+            prefix: ends,
+            cycle_start,
+            cycle_len: t - cycle_start,
+            cycle,
+        }
+    }
+
+    /// Returns all times at which the ghost is at an end node, in increasing
+    /// order.
+    fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        let cycles = (0..).flat_map(move |n| {
+            self.cycle
+                .iter()
+                .map(move |offset| offset + n * self.cycle_len)
+        });
+
+        self.prefix
+            .iter()
+            .copied()
+            .chain(cycles.take_while(|_| !self.cycle.is_empty()))
+    }
+
+    fn is_end_at(&self, t: u64) -> bool {
+        if t < self.cycle_start {
+            self.prefix.contains(&t)
+        } else {
+            let offset = self.cycle_start + (t - self.cycle_start) % self.cycle_len;
+            self.cycle.contains(&offset)
+        }
+    }
+}
+
+fn step(node: Node, instr: Dir, map: &Map) -> Node {
+    let (left, right) = map.node_map.get(&node).copied().unwrap();
+
+    match instr {
+        R => right,
+        L => left,
+    }
 }
 
 fn traverse(start: Node, map: &Map) -> Node {
     let mut current = start;
 
-    for instr in &map.instrs {
-        let (left, right) = map.node_map.get(&current).copied().unwrap();
-
-        current = match instr {
-            R => right,
-            L => left,
-        };
+    for &instr in &map.instrs {
+        current = step(current, instr, map);
     }
 
     current
@@ -160,7 +262,7 @@ impl FromStr for Node {
         s.as_bytes()
             .try_into()
             .map(Self)
-            .map_err(|_| "invalid node")
+            .map_err(|_| "three-character node name")
     }
 }
 
@@ -169,3 +271,129 @@ struct Map {
     instrs: Vec<Dir>,
     node_map: HashMap<Node, (Node, Node)>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE_2: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE_3: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse_input(EXAMPLE_1).unwrap()), Some(2));
+        assert_eq!(part1(&parse_input(EXAMPLE_2).unwrap()), Some(6));
+    }
+
+    #[test]
+    fn part1_no_start() {
+        assert_eq!(part1(&parse_input(EXAMPLE_3).unwrap()), None);
+    }
+
+    #[test]
+    fn part1_unreachable_end() {
+        let map = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(part1(&parse_input(map).unwrap()), None);
+    }
+
+    #[test]
+    fn undefined_child() {
+        let err = parse_input("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 8));
+    }
+
+    #[test]
+    fn truncated_node() {
+        let err = parse_input("LR\n\nAAA = (AAA, AAA)\nBBB = (AAA").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 11));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse_input(EXAMPLE_3).unwrap()), Some(6));
+    }
+
+    #[test]
+    fn part2_general_cycles() {
+        // 11A reaches the cycle after 2 steps, but the cycle has length 3;
+        // 33A has two end nodes in its cycle
+        let map = "\
+L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11D, 11D)
+11D = (11Z, 11Z)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22Z, 22Z)
+33A = (33B, 33B)
+33B = (33Z, 33Z)
+33Z = (3XZ, 3XZ)
+3XZ = (33C, 33C)
+33C = (33D, 33D)
+33D = (33Z, 33Z)";
+
+        assert_eq!(part2(&parse_input(map).unwrap()), Some(11));
+    }
+
+    #[test]
+    fn part2_end_before_cycle() {
+        // 11A is only ever at an end node after 1 step
+        let map = "\
+L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22Z, 22Z)";
+
+        assert_eq!(part2(&parse_input(map).unwrap()), Some(1));
+    }
+
+    #[test]
+    fn part2_never_all_at_end() {
+        let map = "\
+L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22C, 22C)
+22C = (22Z, 22Z)";
+
+        assert_eq!(part2(&parse_input(map).unwrap()), None);
+    }
+}
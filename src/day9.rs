@@ -1,78 +1,83 @@
 use aoc_runner_derive::aoc;
 
-fn parse_input_into(line: &str, row: &mut Vec<i32>) {
+use crate::utils::{
+    parse::{Cursor, ParseError},
+    sequence::{extrapolate, extrapolate_back},
+};
+
+fn parse_input_into(line: Cursor, row: &mut Vec<i32>) -> Result<(), ParseError> {
     row.clear();
-    row.extend(line.split_whitespace().map(|n| n.parse::<i32>().unwrap()));
+    for n in line.ints() {
+        row.push(n?);
+    }
+    if row.is_empty() {
+        return Err(line.error("integer"));
+    }
+    Ok(())
 }
 
 #[aoc(day9, part1)]
-fn part1(s: &str) -> i32 {
-    s.lines()
-        .scan(Vec::with_capacity(1000), |row, line| {
-            parse_input_into(line, row);
-            Some(extrapolate(row))
+fn part1(s: &str) -> Result<i32, ParseError> {
+    let mut row = Vec::with_capacity(1000);
+    Cursor::new(9, s)
+        .lines()
+        .map(|line| {
+            parse_input_into(line, &mut row)?;
+            Ok(extrapolate(&mut row))
         })
         .sum()
 }
 
 #[aoc(day9, part2)]
-fn part2(s: &str) -> i32 {
-    s.lines()
-        .scan(Vec::with_capacity(1000), |row, line| {
-            parse_input_into(line, row);
-            Some(extrapolate_back(row))
+fn part2(s: &str) -> Result<i32, ParseError> {
+    let mut row = Vec::with_capacity(1000);
+    Cursor::new(9, s)
+        .lines()
+        .map(|line| {
+            parse_input_into(line, &mut row)?;
+            Ok(extrapolate_back(&mut row))
         })
         .sum()
 }
 
 #[aoc(day9, part2, reverse)]
-fn part2_reverse(s: &str) -> i32 {
-    s.lines()
-        .scan(Vec::with_capacity(1000), |row, line| {
-            parse_input_into(line, row);
+fn part2_reverse(s: &str) -> Result<i32, ParseError> {
+    let mut row = Vec::with_capacity(1000);
+    Cursor::new(9, s)
+        .lines()
+        .map(|line| {
+            parse_input_into(line, &mut row)?;
             row.reverse();
-            Some(extrapolate(row))
+            Ok(extrapolate(&mut row))
         })
         .sum()
 }
 
-fn extrapolate(row: &mut [i32]) -> i32 {
-    for i in 0..row.len() {
-        if is_constant(&row[i..]) {
-            return row[0..=i].iter().sum();
-        }
-
-        let last = *row.last().unwrap();
-        for j in (i + 1..row.len()).rev() {
-            row[j] -= row[j - 1];
-        }
-        row[i] = last;
-    }
-    unreachable!()
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn extrapolate_back(row: &mut [i32]) -> i32 {
-    for i in 0..row.len() {
-        if is_constant(&row[i..]) {
-            return row[0..=i]
-                .iter()
-                .enumerate()
-                .map(|(i, n)| if i % 2 == 0 { *n } else { -*n })
-                .sum();
-        }
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
 
-        for j in (i + 1..row.len()).rev() {
-            row[j] -= row[j - 1];
-        }
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(114));
     }
-    unreachable!()
-}
 
-fn is_constant(row: &[i32]) -> bool {
-    if row.len() < 2 {
-        return true;
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(2));
+        assert_eq!(part2_reverse(EXAMPLE), Ok(2));
     }
 
-    let first = row[0];
-    row[1..].iter().all(|&n| n == first)
+    #[test]
+    fn blank_line() {
+        let err = part1("0 3 6\n\n1 3 6").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = part1("0 3 6\n1 3 x").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+    }
 }
@@ -7,10 +7,10 @@ use std::cmp::Ordering;
 /// - `start`: The start of the range to search.
 /// - `end`: The end of the range to search.
 /// - `cmp(t)`: A function that compares `t` to the desired value.
-///             It returns `Ordering::Less` if `t` is less than the desired
-///             value, `Ordering::Greater` if `t` is greater than the desired
-///             value, and `Ordering::Equal` if `t` is equal to the desired
-///             value.
+///   It returns `Ordering::Less` if `t` is less than the desired
+///   value, `Ordering::Greater` if `t` is greater than the desired
+///   value, and `Ordering::Equal` if `t` is equal to the desired
+///   value.
 pub fn binary_search_range<T: Ord + Midpoint>(
     mut start: T,
     mut end: T,
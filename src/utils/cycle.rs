@@ -0,0 +1,118 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// The sequence of states visited by repeatedly applying a step function to
+/// an initial state, which eventually repeats.
+#[derive(Clone, Debug)]
+pub struct Cycle<T> {
+    /// Every distinct state, in the order they were first visited.
+    states: Vec<T>,
+    /// The index of the first state in the cycle.
+    pub start: usize,
+    /// The number of states in the cycle.
+    pub len: usize,
+}
+
+impl<T: Clone + Eq + Hash> Cycle<T> {
+    /// Applies `step` to `initial` until a state repeats.
+    ///
+    /// This never terminates if the sequence of states does not repeat.
+    pub fn find(initial: T, step: impl FnMut(&T) -> T) -> Self {
+        match run(initial, step, None) {
+            Ok(cycle) => cycle,
+            Err(_) => unreachable!("no step limit"),
+        }
+    }
+}
+
+impl<T> Cycle<T> {
+    /// Returns every distinct state, in the order they were first visited.
+    pub fn states(&self) -> &[T] {
+        &self.states
+    }
+
+    /// Returns the index in [`states`](Self::states) of the state after `n`
+    /// steps.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// Returns the state after `n` steps.
+    pub fn nth(&self, n: usize) -> &T {
+        &self.states[self.index(n)]
+    }
+}
+
+/// Returns the state after applying `step` to `initial` `n` times.
+///
+/// Once a state repeats, the remaining steps are skipped by projecting `n`
+/// onto the cycle.
+pub fn nth_state<T: Clone + Eq + Hash>(initial: T, n: usize, step: impl FnMut(&T) -> T) -> T {
+    match run(initial, step, Some(n)) {
+        Ok(cycle) => cycle.nth(n).clone(),
+        Err(state) => state,
+    }
+}
+
+/// Applies `step` to `initial` until a state repeats, or until `limit` steps
+/// have been taken, in which case the last state is returned as an error.
+fn run<T: Clone + Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&T) -> T,
+    limit: Option<usize>,
+) -> Result<Cycle<T>, T> {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Ok(Cycle {
+                len: states.len() - start,
+                start,
+                states,
+            });
+        }
+        if limit == Some(states.len()) {
+            return Err(state);
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find() {
+        // 1, 2, 4, 8, 16, 32, 64, 28, 56, 12, 24, 48, 96, 92, 84, 68, 36, 72, 44, 88, 76, 52, 4
+        let cycle = Cycle::find(1, |n| n * 2 % 100);
+        assert_eq!((cycle.start, cycle.len), (2, 20));
+        assert_eq!(*cycle.nth(1), 2);
+        assert_eq!(*cycle.nth(22), 4);
+        assert_eq!(*cycle.nth(1_000_000_000), 76);
+    }
+
+    #[test]
+    fn nth_state_before_cycle() {
+        let mut steps = 0;
+        let state = nth_state(0u64, 5, |n| {
+            steps += 1;
+            n + 1
+        });
+        assert_eq!((state, steps), (5, 5));
+    }
+
+    #[test]
+    fn nth_state_after_cycle() {
+        assert_eq!(nth_state(1, 1_000_000_000, |n| n * 2 % 100), 76);
+    }
+}
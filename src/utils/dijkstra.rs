@@ -0,0 +1,79 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use super::num::Int;
+
+/// Returns the cost of the cheapest path from any of the `starts` to a state
+/// for which `is_goal` returns true, or `None` if no goal can be reached.
+///
+/// `successors(state)` returns the states reachable from `state` in a single
+/// step, together with the (non-negative) cost of that step.
+pub fn shortest_path<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<C>
+where
+    S: Clone + Eq + Hash,
+    C: Int,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // the queue refers to states by their index in `states`, so that states
+    // don't need to be ordered
+    let mut best = HashMap::new();
+    let mut states = Vec::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if best.insert(start.clone(), C::ZERO).is_none() {
+            queue.push(Reverse((C::ZERO, states.len())));
+            states.push(start);
+        }
+    }
+
+    while let Some(Reverse((cost, i))) = queue.pop() {
+        let state = states[i].clone();
+        if best[&state] < cost {
+            // a cheaper path to this state has already been handled
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(cost);
+        }
+
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            if best.get(&next).is_some_and(|&best| best <= next_cost) {
+                continue;
+            }
+
+            best.insert(next.clone(), next_cost);
+            queue.push(Reverse((next_cost, states.len())));
+            states.push(next);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cheapest_path() {
+        // 0 -> 1 -> 3 costs 1 + 5, 0 -> 2 -> 3 costs 4 + 1
+        let edges: &[&[(usize, u32)]] = &[&[(1, 1), (2, 4)], &[(3, 5)], &[(3, 1)], &[]];
+        let successors = |&node: &usize| edges[node].iter().copied();
+
+        assert_eq!(shortest_path([0], successors, |&node| node == 3), Some(5));
+        assert_eq!(
+            shortest_path([1, 2], successors, |&node| node == 3),
+            Some(1)
+        );
+        assert_eq!(shortest_path([3], successors, |&node| node == 0), None);
+    }
+}
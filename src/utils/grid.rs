@@ -0,0 +1,446 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug, Display},
+    ops::{Index, IndexMut, Range},
+};
+
+use super::parse::{Cursor, ParseError};
+
+/// A position in a [`Grid`], given as a row and column index.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Returns the position offset by `drow` rows and `dcol` columns, or
+    /// `None` if either index would become negative.
+    pub fn offset(self, drow: isize, dcol: isize) -> Option<Self> {
+        Some(Self {
+            row: self.row.checked_add_signed(drow)?,
+            col: self.col.checked_add_signed(dcol)?,
+        })
+    }
+
+    /// Returns the adjacent position in the direction `dir`, or `None` if
+    /// either index would become negative.
+    pub fn step(self, dir: Dir) -> Option<Self> {
+        let (drow, dcol) = dir.delta();
+        self.offset(drow, dcol)
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+/// One of the four orthogonal directions in a [`Grid`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    /// All directions, in clockwise order starting with `Up`.
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    /// Returns the `(row, column)` offset of a single step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Dir::Up => (-1, 0),
+            Dir::Right => (0, 1),
+            Dir::Down => (1, 0),
+            Dir::Left => (0, -1),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Dir::Left | Dir::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir::Up | Dir::Down)
+    }
+}
+
+/// `(row, column)` offsets of the eight neighbours of a position, in
+/// clockwise order starting with the upper left neighbour.
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+/// A rectangular two-dimensional grid of cells, stored in row-major order.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| Pos::new(row, col)))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.in_bounds(pos) {
+            Some(&self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            Some(&mut self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the adjacent position in the direction `dir`, or `None` if it
+    /// is outside the grid.
+    pub fn step(&self, pos: Pos, dir: Dir) -> Option<Pos> {
+        pos.step(dir).filter(|&pos| self.in_bounds(pos))
+    }
+
+    /// Returns an iterator over the (up to) four orthogonal neighbours of
+    /// `pos` that are inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Returns an iterator over the (up to) eight orthogonal and diagonal
+    /// neighbours of `pos` that are inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |(drow, dcol)| pos.offset(drow, dcol))
+            .filter(|&pos| self.in_bounds(pos))
+    }
+
+    /// Returns an iterator over all positions in the grid, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        self.region(0..self.height, 0..self.width)
+    }
+
+    /// Returns an iterator over the positions in the given rows and columns,
+    /// in row-major order. The region is clipped to the grid bounds.
+    pub fn region(&self, rows: Range<usize>, cols: Range<usize>) -> impl Iterator<Item = Pos> {
+        let rows = rows.start..rows.end.min(self.height);
+        let cols = cols.start..cols.end.min(self.width);
+
+        rows.flat_map(move |row| cols.clone().map(move |col| Pos::new(row, col)))
+    }
+
+    /// Returns an iterator over all positions and cells in the grid, in
+    /// row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Returns the position of the first cell (in row-major order) matching
+    /// the predicate.
+    pub fn position(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find_map(|(pos, cell)| if pred(cell) { Some(pos) } else { None })
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Returns an iterator over the rows of the grid.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a chunk size of zero
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column index out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// Returns an iterator over the columns of the grid.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    /// Returns the grid mirrored along its main diagonal, so that rows become
+    /// columns and vice versa.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[Pos::new(pos.col, pos.row)].clone()
+        })
+    }
+
+    /// Returns the grid rotated a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[Pos::new(self.height - 1 - pos.col, pos.row)].clone()
+        })
+    }
+
+    /// Returns the grid rotated a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[Pos::new(pos.col, self.width - 1 - pos.row)].clone()
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position out of bounds: {pos:?}"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position out of bounds: {pos:?}"))
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid of bytes, with one row per line.
+    ///
+    /// Returns an error if the rows do not all have the same length.
+    pub fn parse(input: Cursor) -> Result<Self, ParseError> {
+        let mut lines = input.lines().peekable();
+
+        let width = lines.peek().map_or(0, |line| line.as_str().len());
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.as_str().len());
+
+        for mut line in lines {
+            let row = line.field(width)?;
+            line.end()?;
+
+            cells.extend_from_slice(row.as_str().as_bytes());
+            height += 1;
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a grid like [`Grid::parse`], but returns an error with the
+    /// description `expected` at the first character that isn't in `tiles`.
+    pub fn parse_tiles(
+        input: Cursor,
+        tiles: &str,
+        expected: impl Into<Cow<'static, str>>,
+    ) -> Result<Self, ParseError> {
+        for mut line in input.lines() {
+            line.take_while(|ch| tiles.contains(ch));
+            if !line.is_empty() {
+                return Err(line.error(expected));
+            }
+        }
+
+        Self::parse(input)
+    }
+}
+
+impl Display for Grid<u8> {
+    /// Formats the grid with one row per line and no trailing newline, so
+    /// that parsing the output gives back the same grid.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            f.write_str(&String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug> Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<u8> {
+        Grid::parse(Cursor::new(0, input)).unwrap()
+    }
+
+    #[test]
+    fn parse_display() {
+        let g = grid("abc\ndef\n");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[Pos::new(1, 0)], b'd');
+        assert_eq!(g.to_string(), "abc\ndef");
+        assert_eq!(grid(&g.to_string()), g);
+    }
+
+    #[test]
+    fn parse_ragged() {
+        let err = Grid::parse(Cursor::new(0, "abc\nde\nfgh")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = Grid::parse(Cursor::new(0, "abc\ndefg")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+    }
+
+    #[test]
+    fn parse_tiles() {
+        let g = Grid::parse_tiles(Cursor::new(0, "#.\n.#"), "#.", "`#` or `.`").unwrap();
+        assert_eq!(g, grid("#.\n.#"));
+
+        let err = Grid::parse_tiles(Cursor::new(0, "#.\n.x#"), "#.", "`#` or `.`").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "`#` or `.`");
+    }
+
+    #[test]
+    fn rotations() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_ccw().to_string(), "cf\nbe\nad");
+
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        assert_eq!(g.rotate_cw().rotate_cw(), g.rotate_ccw().rotate_ccw());
+        assert_eq!(g.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), g);
+        assert_eq!(g.transpose().transpose(), g);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.rows().collect::<Vec<_>>(), [b"abc", b"def"]);
+        assert_eq!(
+            g.columns()
+                .map(|col| col.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            [b"ad", b"be", b"cf"]
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = Grid::new(3, 2, ());
+        let p = Pos::new;
+
+        assert_eq!(
+            g.neighbours4(p(0, 0)).collect::<Vec<_>>(),
+            [p(0, 1), p(1, 0)]
+        );
+        assert_eq!(
+            g.neighbours4(p(1, 2)).collect::<Vec<_>>(),
+            [p(0, 2), p(1, 1)]
+        );
+        assert_eq!(
+            g.neighbours8(p(0, 0)).collect::<Vec<_>>(),
+            [p(0, 1), p(1, 1), p(1, 0)]
+        );
+        assert_eq!(
+            g.neighbours8(p(1, 1)).collect::<Vec<_>>(),
+            [p(0, 0), p(0, 1), p(0, 2), p(1, 2), p(1, 0)]
+        );
+        assert_eq!(g.step(p(1, 2), Dir::Right), None);
+        assert_eq!(g.step(p(1, 2), Dir::Up), Some(p(0, 2)));
+    }
+
+    #[test]
+    fn distances_and_directions() {
+        assert_eq!(Pos::new(1, 5).manhattan_distance(Pos::new(4, 2)), 6);
+        assert_eq!(Pos::new(0, 3).offset(-1, 0), None);
+
+        for dir in Dir::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_ne!(dir.is_horizontal(), dir.is_vertical());
+        }
+    }
+}
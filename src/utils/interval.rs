@@ -0,0 +1,434 @@
+use std::fmt::{self, Debug};
+
+use super::num::Int;
+
+/// A half-open interval `start..end` of integers.
+///
+/// An interval with `start >= end` is empty.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Int> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates the interval `start..start + len`.
+    pub fn with_len(start: T, len: T) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Returns the number of integers in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, n: T) -> bool {
+        self.start <= n && n < self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Returns the interval of integers in both `self` and `other`.
+    ///
+    /// The result may be empty.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Splits the interval into the parts below `at` and at or above `at`.
+    ///
+    /// Either part may be empty.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let at = at.max(self.start).min(self.end.max(self.start));
+        (Self::new(self.start, at), Self::new(at, self.end))
+    }
+
+    /// Splits the interval into the parts before, inside and after `other`.
+    ///
+    /// Any of the parts may be empty.
+    pub fn split_by(&self, other: &Self) -> (Self, Self, Self) {
+        let (before, rest) = self.split_at(other.start);
+        let (inside, after) = rest.split_at(other.end);
+        (before, inside, after)
+    }
+
+    /// Translates the interval so that `from` is moved to `to`.
+    ///
+    /// This is a translation by the offset `to - from`, but written so that it
+    /// works for unsigned types as long as `start >= from`.
+    pub fn translate(&self, from: T, to: T) -> Self {
+        Self::new(self.start - from + to, self.end - from + to)
+    }
+}
+
+impl<T: Debug> Debug for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}..{:?}", self.start, self.end)
+    }
+}
+
+/// A set of integers, represented as a sorted list of disjoint intervals.
+///
+/// Intervals are always coalesced, so no two intervals in the set overlap or
+/// are adjacent to each other, and no interval is empty.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Int> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the total number of integers in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |len, interval| len + interval.len())
+    }
+
+    /// Returns the smallest integer in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// Returns the largest integer in the set.
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(|interval| interval.end - T::ONE)
+    }
+
+    pub fn contains(&self, n: T) -> bool {
+        let i = self.intervals.partition_point(|interval| interval.end <= n);
+        self.intervals
+            .get(i)
+            .is_some_and(|interval| interval.contains(n))
+    }
+
+    /// Returns an iterator over the (coalesced) intervals in the set, in
+    /// ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // find the range of existing intervals that overlap or are adjacent
+        // to the new interval, and merge them into it
+        let lo = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        let hi = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end);
+
+        let merged = if lo < hi {
+            Interval::new(
+                interval.start.min(self.intervals[lo].start),
+                interval.end.max(self.intervals[hi - 1].end),
+            )
+        } else {
+            interval
+        };
+
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+
+            let overlap = a.intersection(&b);
+            if !overlap.is_empty() {
+                intervals.push(overlap);
+            }
+
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+
+        let mut j = 0;
+        for mut a in self.iter() {
+            // skip intervals in `other` that are entirely before `a`
+            while j < other.intervals.len() && other.intervals[j].end <= a.start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < a.end {
+                let (before, _, after) = a.split_by(&other.intervals[k]);
+                if !before.is_empty() {
+                    intervals.push(before);
+                }
+                a = after;
+                k += 1;
+            }
+
+            if !a.is_empty() {
+                intervals.push(a);
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Splits the set into the integers below `at` and at or above `at`.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = Self::new();
+        let mut above = Self::new();
+
+        for interval in self.iter() {
+            let (lo, hi) = interval.split_at(at);
+            below.push_sorted(lo);
+            above.push_sorted(hi);
+        }
+
+        (below, above)
+    }
+
+    /// Splits the set into the integers inside and outside `interval`.
+    pub fn split_by(&self, interval: &Interval<T>) -> (Self, Self) {
+        let mut inside = Self::new();
+        let mut outside = Self::new();
+
+        for other in self.iter() {
+            let (before, overlap, after) = other.split_by(interval);
+            outside.push_sorted(before);
+            inside.push_sorted(overlap);
+            outside.push_sorted(after);
+        }
+
+        (inside, outside)
+    }
+
+    /// Translates every integer in the set so that `from` is moved to `to`.
+    ///
+    /// See [`Interval::translate`].
+    pub fn translate(&self, from: T, to: T) -> Self {
+        Self {
+            intervals: self
+                .iter()
+                .map(|interval| interval.translate(from, to))
+                .collect(),
+        }
+    }
+
+    /// Appends an interval that starts at or after the end of every interval
+    /// in the set, coalescing it with the last interval if they are adjacent.
+    fn push_sorted(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        match self.intervals.last_mut() {
+            Some(last) if last.end >= interval.start => last.end = last.end.max(interval.end),
+            _ => self.intervals.push(interval),
+        }
+    }
+}
+
+impl<T: Int> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Int> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        let mut set = Self::new();
+        set.push_sorted(interval);
+        set
+    }
+}
+
+impl<T: Int> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals = iter
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort_by_key(|interval| interval.start);
+
+        // coalesce overlapping and adjacent intervals
+        let mut set = Self::new();
+        for interval in intervals {
+            set.push_sorted(interval);
+        }
+        set
+    }
+}
+
+impl<T: Int> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        *self = self.iter().chain(iter).collect();
+    }
+}
+
+impl<T: Debug> Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.intervals).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    fn intervals(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter()
+            .map(|interval| (interval.start, interval.end))
+            .collect()
+    }
+
+    /// Returns the integers in the set, checking that its intervals are
+    /// sorted, non-empty and coalesced.
+    fn model(set: &IntervalSet<i32>) -> BTreeSet<i32> {
+        for (a, b) in set.iter().zip(set.iter().skip(1)) {
+            assert!(a.end < b.start, "not coalesced: {set:?}");
+        }
+        assert!(set.iter().all(|interval| !interval.is_empty()), "{set:?}");
+
+        set.iter()
+            .flat_map(|interval| interval.start..interval.end)
+            .collect()
+    }
+
+    fn interval() -> impl Strategy<Value = Interval<i32>> {
+        (0..40, -2..8).prop_map(|(start, len)| Interval::with_len(start, len))
+    }
+
+    fn interval_set() -> impl Strategy<Value = IntervalSet<i32>> {
+        prop::collection::vec(interval(), 0..6).prop_map(IntervalSet::from_iter)
+    }
+
+    #[test]
+    fn interval_split() {
+        let interval = Interval::new(2, 8);
+        assert_eq!(interval.split_at(0), (Interval::new(2, 2), interval));
+        assert_eq!(
+            interval.split_at(5),
+            (Interval::new(2, 5), Interval::new(5, 8))
+        );
+        assert_eq!(
+            interval.split_by(&Interval::new(4, 10)),
+            (
+                Interval::new(2, 4),
+                Interval::new(4, 8),
+                Interval::new(8, 8)
+            )
+        );
+    }
+
+    #[test]
+    fn coalesce_adjacent() {
+        assert_eq!(intervals(&set(&[(5, 7), (0, 2), (2, 5)])), [(0, 7)]);
+        assert_eq!(intervals(&set(&[(0, 2), (3, 5), (4, 4)])), [(0, 2), (3, 5)]);
+
+        let mut set = set(&[(0, 2), (6, 8)]);
+        set.insert(Interval::new(2, 4));
+        assert_eq!(intervals(&set), [(0, 4), (6, 8)]);
+        set.insert(Interval::new(4, 6));
+        assert_eq!(intervals(&set), [(0, 8)]);
+    }
+
+    proptest! {
+        #[test]
+        fn set_operations(a in interval_set(), b in interval_set()) {
+            let (ma, mb) = (model(&a), model(&b));
+
+            prop_assert_eq!(model(&a.union(&b)), &ma | &mb);
+            prop_assert_eq!(model(&a.intersection(&b)), &ma & &mb);
+            prop_assert_eq!(model(&a.difference(&b)), &ma - &mb);
+
+            let mut extended = a.clone();
+            extended.extend(b.iter());
+            prop_assert_eq!(model(&extended), &ma | &mb);
+
+            prop_assert_eq!(a.len() as usize, ma.len());
+            prop_assert_eq!(a.min(), ma.first().copied());
+            prop_assert_eq!(a.max(), ma.last().copied());
+            for n in -1..50 {
+                prop_assert_eq!(a.contains(n), ma.contains(&n));
+            }
+        }
+
+        #[test]
+        fn insert(a in interval_set(), interval in interval()) {
+            let mut expected = model(&a);
+            expected.extend(interval.start..interval.end);
+
+            let mut set = a;
+            set.insert(interval);
+            prop_assert_eq!(model(&set), expected);
+        }
+
+        #[test]
+        fn split(a in interval_set(), interval in interval(), at in -1..50) {
+            let ma = model(&a);
+
+            let (below, above) = a.split_at(at);
+            prop_assert_eq!(model(&below), ma.range(..at).copied().collect::<BTreeSet<_>>());
+            prop_assert_eq!(model(&above), ma.range(at..).copied().collect::<BTreeSet<_>>());
+
+            let (inside, outside) = a.split_by(&interval);
+            let (inside_model, outside_model) = ma
+                .iter()
+                .partition::<BTreeSet<_>, _>(|&&n| interval.contains(n));
+            prop_assert_eq!(model(&inside), inside_model);
+            prop_assert_eq!(model(&outside), outside_model);
+
+            let translated = a.translate(at, 2 * at);
+            prop_assert_eq!(model(&translated), ma.iter().map(|n| n + at).collect::<BTreeSet<_>>());
+        }
+    }
+}
@@ -1,2 +1,9 @@
 pub mod binary_search;
+pub mod cycle;
+pub mod dijkstra;
+pub mod grid;
+pub mod interval;
 pub mod num;
+pub mod parse;
+pub mod polygon;
+pub mod sequence;
@@ -1,6 +1,8 @@
 use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
     mem,
-    ops::{Div, Mul, Rem},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
 };
 
 pub fn gcd<T: Int>(mut a: T, mut b: T) -> T {
@@ -16,16 +18,214 @@ pub fn lcm<T: Int>(a: T, b: T) -> T {
     (a / gcd(a, b)) * b
 }
 
+/// Returns `(g, x, y)` such that `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = ext_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Combines the congruences `t = r1 (mod m1)` and `t = r2 (mod m2)` into a
+/// single congruence `t = r (mod lcm(m1, m2))`, and returns `(r, lcm(m1, m2))`
+/// with `0 <= r < lcm(m1, m2)`.
+///
+/// This is the generalized Chinese remainder theorem, so the moduli do not
+/// need to be coprime. Returns `None` if the congruences have no common
+/// solution.
+pub fn crt(r1: i128, m1: i128, r2: i128, m2: i128) -> Option<(i128, i128)> {
+    let (g, x, _) = ext_gcd(m1, m2);
+    if (r2 - r1) % g != 0 {
+        return None;
+    }
+
+    // t = r1 + m1 * k, where m1 * k = r2 - r1 (mod m2)
+    let m = m2 / g;
+    let k = ((r2 - r1) / g % m * (x % m)) % m;
+    let lcm = m1 * m;
+
+    Some(((r1 + m1 * k).rem_euclid(lcm), lcm))
+}
+
+/// Returns the integer square root of `n`, i.e. the largest integer `r` such
+/// that `r * r <= n`.
+///
+/// Panics if `n` is negative.
+pub fn isqrt<T: Int>(n: T) -> T {
+    assert!(n >= T::ZERO, "square root of negative number");
+
+    let two = T::ONE + T::ONE;
+    if n < two {
+        return n;
+    }
+
+    // Newton's method, starting from an overestimate of the root; the
+    // estimate decreases monotonically until it reaches the root
+    let mut x = n / two + T::ONE;
+    loop {
+        let y = (x + n / x) / two;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// An exact fraction of `i128`s.
+///
+/// Fractions are always kept in lowest terms with a positive denominator, so
+/// equal values have equal representations. Arithmetic panics on overflow in
+/// debug builds, like the underlying integers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { numer: 0, denom: 1 };
+    pub const ONE: Self = Self { numer: 1, denom: 1 };
+
+    /// Creates the fraction `numer / denom`.
+    ///
+    /// Panics if `denom` is zero.
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "fraction with zero denominator");
+
+        let g = gcd(numer, denom).abs() * denom.signum();
+        Self {
+            numer: numer / g,
+            denom: denom / g,
+        }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(self) -> i128 {
+        self.denom
+    }
+
+    /// Returns `1 / self`.
+    ///
+    /// Panics if `self` is zero.
+    pub fn recip(self) -> Self {
+        Self::new(self.denom, self.numer)
+    }
+
+    /// Returns the value as an integer, or `None` if it isn't one.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denom == 1).then_some(self.numer)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Self { numer: n, denom: 1 }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // scaling to the lcm rather than the product of the denominators
+        // keeps intermediate values small
+        let g = gcd(self.denom, other.denom);
+        Self::new(
+            self.numer * (other.denom / g) + other.numer * (self.denom / g),
+            self.denom / g * other.denom,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        // cancel common factors before multiplying
+        let g1 = gcd(self.numer, other.denom).abs();
+        let g2 = gcd(other.numer, self.denom).abs();
+        Self::new(
+            (self.numer / g1) * (other.numer / g2),
+            (self.denom / g2) * (other.denom / g1),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    /// Panics if `other` is zero.
+    fn div(self, other: Self) -> Self {
+        self.mul(other.recip())
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are positive, so cross-multiplying preserves the order
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
 pub trait Int:
-    Copy + Eq + Ord + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+    Copy
+    + Eq
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
 {
     const ZERO: Self;
+    const ONE: Self;
 }
 
 macro_rules! impl_int {
     ($type:ty) => {
         impl Int for $type {
             const ZERO: Self = 0;
+            const ONE: Self = 1;
         }
     };
 }
@@ -43,3 +243,86 @@ impl_int!(i32);
 impl_int!(i64);
 impl_int!(i128);
 impl_int!(isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crt_coprime() {
+        assert_eq!(crt(2, 3, 3, 5), Some((8, 15)));
+        assert_eq!(crt(8, 15, 2, 7), Some((23, 105)));
+    }
+
+    #[test]
+    fn crt_not_coprime() {
+        assert_eq!(crt(2, 6, 8, 10), Some((8, 30)));
+        assert_eq!(crt(1, 6, 2, 4), None);
+        assert_eq!(crt(3, 4, 3, 4), Some((3, 4)));
+    }
+
+    #[test]
+    fn isqrt_small() {
+        for n in 0..10_000u32 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+    }
+
+    #[test]
+    fn isqrt_large() {
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(
+            isqrt((u64::MAX as u128) * (u64::MAX as u128)),
+            u64::MAX as u128
+        );
+        assert_eq!(
+            isqrt((u64::MAX as u128) * (u64::MAX as u128) - 1),
+            u64::MAX as u128 - 1
+        );
+        assert_eq!(isqrt(i64::MAX), 3037000499);
+    }
+
+    #[test]
+    fn rational_lowest_terms() {
+        let r = Rational::new(4, -6);
+        assert_eq!((r.numer(), r.denom()), (-2, 3));
+        assert_eq!(Rational::new(0, -5), Rational::ZERO);
+        assert_eq!(Rational::new(-12, -4).to_integer(), Some(3));
+        assert_eq!(Rational::new(1, 2).to_integer(), None);
+    }
+
+    #[test]
+    fn rational_arithmetic() {
+        let r = Rational::new;
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 6) + r(1, 3), r(1, 2));
+        assert_eq!(r(1, 2) - r(3, 4), r(-1, 4));
+        assert_eq!(r(2, 3) * r(3, 4), r(1, 2));
+        assert_eq!(r(1, 2) / r(-1, 4), Rational::from(-2));
+        assert_eq!(r(-2, 5).recip(), r(-5, 2));
+        assert_eq!(r(7, 3).to_string(), "7/3");
+        assert_eq!(Rational::from(-7).to_string(), "-7");
+    }
+
+    #[test]
+    fn rational_ordering() {
+        let r = Rational::new;
+        assert!(r(-1, 2) < r(1, 3));
+        assert!(r(2, 3) > r(3, 5));
+        assert!(r(-2, 3) < r(-3, 5));
+        assert_eq!(r(2, 4).cmp(&r(1, 2)), Ordering::Equal);
+    }
+
+    #[test]
+    fn rational_large() {
+        // cancelling before multiplying avoids overflowing 10^40
+        let big = 10i128.pow(20);
+        assert_eq!(Rational::new(big, 3) * Rational::new(3, big), Rational::ONE);
+        assert_eq!(
+            Rational::new(1, big) + Rational::new(1, big),
+            Rational::new(2, big)
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// An error produced when puzzle input could not be parsed, with the location
+/// in the input where parsing failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number in characters, starting at 1.
+    pub column: usize,
+    /// Description of what was expected at this location.
+    pub expected: Cow<'static, str>,
+}
+
+impl ParseError {
+    /// Creates an error located at the start of `at`, which must be a slice
+    /// of `input`.
+    pub fn at(day: u32, input: &str, at: &str, expected: impl Into<Cow<'static, str>>) -> Self {
+        let offset = (at.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        assert!(offset <= input.len(), "location is not within the input");
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error located right after the end of `at`, which must be a
+    /// slice of `input`.
+    pub fn after(day: u32, input: &str, at: &str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self::at(day, input, &at[at.len()..], expected)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}: parse error at line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A cursor into puzzle input.
+///
+/// Parsers consume input from the front of the cursor, and return cursors
+/// over the parts they split off. Every cursor remembers the full input it
+/// was created from, so that any error can be reported with its line and
+/// column. Cursors only borrow the input and never allocate, except when
+/// creating an error.
+#[derive(Copy, Clone, Debug)]
+pub struct Cursor<'a> {
+    day: u32,
+    input: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+    /// Creates a cursor at the start of the input for the given day.
+    pub fn new(day: u32, input: &'a str) -> Self {
+        Self {
+            day,
+            input,
+            rest: input,
+        }
+    }
+
+    /// Returns the remaining, unparsed input.
+    pub fn as_str(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// Returns an error located at the current position.
+    pub fn error(&self, expected: impl Into<Cow<'static, str>>) -> ParseError {
+        ParseError::at(self.day, self.input, self.rest, expected)
+    }
+
+    /// Returns an error located at the end of the remaining input.
+    pub fn error_at_end(&self, expected: impl Into<Cow<'static, str>>) -> ParseError {
+        ParseError::after(self.day, self.input, self.rest, expected)
+    }
+
+    /// Returns a cursor over `s`, which must be a slice of the remaining input.
+    fn sub(&self, s: &'a str) -> Self {
+        Self { rest: s, ..*self }
+    }
+
+    /// Splits off the first `n` bytes of the remaining input.
+    fn take(&mut self, n: usize) -> Self {
+        let (taken, rest) = self.rest.split_at(n);
+        self.rest = rest;
+        self.sub(taken)
+    }
+
+    /// Returns an error unless all input has been consumed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of input"))
+        }
+    }
+
+    /// Returns the cursor with leading and trailing whitespace removed.
+    pub fn trim(self) -> Self {
+        self.sub(self.rest.trim())
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consumes `tag` from the start of the input.
+    pub fn tag(&mut self, tag: &str) -> Result<(), ParseError> {
+        match self.rest.strip_prefix(tag) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.error(format!("`{tag}`"))),
+        }
+    }
+
+    /// Consumes the longest prefix whose characters all match `pred`.
+    pub fn take_while(&mut self, mut pred: impl FnMut(char) -> bool) -> Self {
+        let len = self.rest.find(|ch| !pred(ch)).unwrap_or(self.rest.len());
+        self.take(len)
+    }
+
+    /// Consumes the input up to the next `delim`, and the delimiter itself.
+    ///
+    /// Returns the input before the delimiter.
+    pub fn take_until(&mut self, delim: &str) -> Result<Self, ParseError> {
+        match self.rest.find(delim) {
+            Some(i) => {
+                let taken = self.take(i);
+                self.rest = &self.rest[delim.len()..];
+                Ok(taken)
+            }
+            None => Err(self.error_at_end(format!("`{delim}`"))),
+        }
+    }
+
+    /// Consumes a fixed-width field of `width` bytes.
+    pub fn field(&mut self, width: usize) -> Result<Self, ParseError> {
+        if width <= self.rest.len() && self.rest.is_char_boundary(width) {
+            Ok(self.take(width))
+        } else {
+            Err(self.error_at_end(format!("field of width {width}")))
+        }
+    }
+
+    /// Consumes a non-empty word, ending at whitespace or the end of input,
+    /// and any whitespace after it.
+    pub fn word(&mut self) -> Result<Self, ParseError> {
+        let word = self.take_while(|ch| !ch.is_whitespace());
+        if word.is_empty() {
+            return Err(self.error("word"));
+        }
+        self.skip_whitespace();
+        Ok(word)
+    }
+
+    /// Consumes an integer, with an optional leading sign.
+    pub fn int<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let sign = self.rest.starts_with(['+', '-']) as usize;
+        let len = sign
+            + self.rest[sign..]
+                .find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(self.rest.len() - sign);
+
+        match self.rest[..len].parse() {
+            Ok(n) => {
+                self.take(len);
+                Ok(n)
+            }
+            Err(_) => Err(self.error("integer")),
+        }
+    }
+
+    /// Consumes a parenthesized group `( ... )`, and returns the input
+    /// between the parentheses.
+    ///
+    /// Parentheses are not allowed to nest.
+    pub fn parens(&mut self) -> Result<Self, ParseError> {
+        self.tag("(")?;
+        self.take_until(")")
+    }
+
+    /// Parses the remaining input as a `T` using its [`FromStr`]
+    /// implementation.
+    pub fn parse<T: FromStr>(
+        self,
+        expected: impl Into<Cow<'static, str>>,
+    ) -> Result<T, ParseError> {
+        self.rest.parse().map_err(|_| self.error(expected))
+    }
+
+    /// Splits the input at the first `delim`.
+    pub fn split_once(self, delim: &str) -> Result<(Self, Self), ParseError> {
+        let mut rest = self;
+        let first = rest.take_until(delim)?;
+        Ok((first, rest))
+    }
+
+    /// Splits a `key: value` header into its key and value.
+    pub fn key_value(self) -> Result<(Self, Self), ParseError> {
+        let (key, value) = self.split_once(":")?;
+        Ok((key, value.trim()))
+    }
+
+    /// Returns an iterator over the parts of the input separated by `delim`,
+    /// with leading and trailing whitespace removed from each part.
+    pub fn delimited(self, delim: &'a str) -> impl Iterator<Item = Self> + 'a {
+        self.rest
+            .split(delim)
+            .map(move |part| self.sub(part).trim())
+    }
+
+    /// Returns an iterator over the lines of the input.
+    pub fn lines(self) -> impl Iterator<Item = Self> + 'a {
+        self.rest.lines().map(move |line| self.sub(line))
+    }
+
+    /// Returns an iterator over the blocks of the input that are separated
+    /// by blank lines.
+    pub fn blocks(self) -> impl Iterator<Item = Self> + 'a {
+        self.rest
+            .split("\n\n")
+            .map(move |block| self.sub(block.trim_matches('\n')))
+            .filter(|block| !block.is_empty())
+    }
+
+    /// Returns an iterator over the whitespace-separated integers in the
+    /// input.
+    pub fn ints<T: FromStr + 'a>(self) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+        self.rest.split_whitespace().map(move |word| {
+            let mut word = self.sub(word);
+            let n = word.int()?;
+            word.end()?;
+            Ok(n)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(err: ParseError) -> (usize, usize) {
+        (err.line, err.column)
+    }
+
+    #[test]
+    fn error_position() {
+        let input = "ab\ncdé\nfg";
+        let at = |offset| ParseError::at(1, input, &input[offset..], "x");
+        assert_eq!(position(at(0)), (1, 1));
+        assert_eq!(position(at(2)), (1, 3));
+        assert_eq!(position(at(3)), (2, 1));
+        // columns count characters, not bytes
+        assert_eq!(position(at(7)), (2, 4));
+        assert_eq!(position(at(8)), (3, 1));
+
+        let err = ParseError::after(1, input, &input[3..7], "x");
+        assert_eq!(position(err.clone()), (2, 4));
+        assert_eq!(
+            err.to_string(),
+            "day 1: parse error at line 2, column 4: expected x"
+        );
+    }
+
+    #[test]
+    fn int_sign() {
+        let mut cursor = Cursor::new(0, "+12 -7 -x");
+        assert_eq!(cursor.int::<i32>(), Ok(12));
+        cursor.skip_whitespace();
+        assert_eq!(cursor.int::<i32>(), Ok(-7));
+        cursor.skip_whitespace();
+        assert_eq!(position(cursor.int::<i32>().unwrap_err()), (1, 8));
+
+        let mut cursor = Cursor::new(0, "-3");
+        assert_eq!(position(cursor.int::<u32>().unwrap_err()), (1, 1));
+        assert_eq!(cursor.as_str(), "-3");
+
+        let mut cursor = Cursor::new(0, "42abc");
+        assert_eq!(cursor.int::<u8>(), Ok(42));
+        assert_eq!(cursor.as_str(), "abc");
+    }
+
+    #[test]
+    fn take_until() {
+        let mut cursor = Cursor::new(0, "a -> b -> c");
+        assert_eq!(cursor.take_until(" -> ").unwrap().as_str(), "a");
+        assert_eq!(cursor.take_until(" -> ").unwrap().as_str(), "b");
+        assert_eq!(position(cursor.take_until(" -> ").unwrap_err()), (1, 12));
+        assert_eq!(cursor.as_str(), "c");
+
+        let (key, value) = Cursor::new(0, "key:  value ").key_value().unwrap();
+        assert_eq!((key.as_str(), value.as_str()), ("key", "value"));
+        let err = Cursor::new(0, "a\nb c").split_once("=").unwrap_err();
+        assert_eq!(position(err), (2, 4));
+    }
+
+    #[test]
+    fn tag_and_end() {
+        let mut cursor = Cursor::new(0, "Game 12");
+        assert_eq!(position(cursor.tag("Card").unwrap_err()), (1, 1));
+        cursor.tag("Game ").unwrap();
+        assert_eq!(position(cursor.end().unwrap_err()), (1, 6));
+        assert_eq!(cursor.word().unwrap().as_str(), "12");
+        assert!(cursor.end().is_ok());
+        assert_eq!(position(cursor.word().unwrap_err()), (1, 8));
+    }
+
+    #[test]
+    fn field() {
+        let mut cursor = Cursor::new(0, "éab");
+        assert_eq!(position(cursor.field(1).unwrap_err()), (1, 4));
+        assert_eq!(cursor.field(2).unwrap().as_str(), "é");
+        assert_eq!(position(cursor.field(3).unwrap_err()), (1, 4));
+        assert_eq!(cursor.field(2).unwrap().as_str(), "ab");
+    }
+
+    #[test]
+    fn parens() {
+        let mut cursor = Cursor::new(0, "(a, b) c");
+        assert_eq!(cursor.parens().unwrap().as_str(), "a, b");
+        assert_eq!(cursor.as_str(), " c");
+        assert_eq!(position(Cursor::new(0, "(a").parens().unwrap_err()), (1, 3));
+    }
+
+    #[test]
+    fn blocks() {
+        let blocks = Cursor::new(0, "\na\nb\n\n\nc\n\n")
+            .blocks()
+            .map(|block| block.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, ["a\nb", "c"]);
+
+        let block = Cursor::new(0, "a\n\n\nc").blocks().nth(1).unwrap();
+        assert_eq!(position(block.error("x")), (4, 1));
+    }
+
+    #[test]
+    fn delimited() {
+        let parts = Cursor::new(0, "1 red,  2 blue , ")
+            .delimited(",")
+            .map(|part| part.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(parts, ["1 red", "2 blue", ""]);
+    }
+
+    #[test]
+    fn ints() {
+        let ints = Cursor::new(0, " 1  -2\t3 ")
+            .ints()
+            .collect::<Result<Vec<i32>, _>>();
+        assert_eq!(ints, Ok(vec![1, -2, 3]));
+
+        let err = Cursor::new(0, "1 2x 3")
+            .ints::<i32>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(position(err), (1, 4));
+    }
+}
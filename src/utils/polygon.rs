@@ -0,0 +1,57 @@
+use super::num::gcd;
+
+/// Returns twice the area enclosed by the simple polygon with the given
+/// vertices, in order, using the shoelace formula.
+///
+/// Twice the area of a lattice polygon is always an integer. The vertices may
+/// be in either clockwise or counter-clockwise order.
+pub fn double_area(vertices: &[(i64, i64)]) -> i64 {
+    let next = vertices.iter().cycle().skip(1);
+
+    vertices
+        .iter()
+        .zip(next)
+        .map(|(&(x1, y1), &(x2, y2))| x1 * y2 - x2 * y1)
+        .sum::<i64>()
+        .abs()
+}
+
+/// Returns the number of lattice points on the boundary of the polygon with
+/// the given vertices.
+pub fn boundary_points(vertices: &[(i64, i64)]) -> i64 {
+    let next = vertices.iter().cycle().skip(1);
+
+    vertices
+        .iter()
+        .zip(next)
+        .map(|(&(x1, y1), &(x2, y2))| gcd(x1.abs_diff(x2), y1.abs_diff(y2)) as i64)
+        .sum()
+}
+
+/// Returns the number of lattice points strictly inside the polygon with the
+/// given vertices.
+pub fn interior_points(vertices: &[(i64, i64)]) -> i64 {
+    // by Pick's theorem, A = i + b/2 - 1
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangle() {
+        let vertices = [(0, 0), (0, 3), (2, 3), (2, 0)];
+        assert_eq!(double_area(&vertices), 12);
+        assert_eq!(boundary_points(&vertices), 10);
+        assert_eq!(interior_points(&vertices), 2);
+    }
+
+    #[test]
+    fn triangle() {
+        let vertices = [(0, 0), (4, 0), (0, 4)];
+        assert_eq!(double_area(&vertices), 16);
+        assert_eq!(boundary_points(&vertices), 12);
+        assert_eq!(interior_points(&vertices), 3);
+    }
+}
@@ -0,0 +1,96 @@
+use super::num::Int;
+
+/// Returns the next value of a sequence given by a polynomial, by taking
+/// finite differences until they become constant.
+///
+/// `row` is overwritten with the differences. Panics if `row` is empty.
+pub fn extrapolate<T: Int>(row: &mut [T]) -> T {
+    let levels = last_differences(row);
+    row[..levels].iter().fold(T::ZERO, |sum, &n| sum + n)
+}
+
+/// Returns the value before the first value of a sequence given by a
+/// polynomial, by taking finite differences until they become constant.
+///
+/// `row` is overwritten with the differences. Panics if `row` is empty.
+pub fn extrapolate_back<T: Int>(row: &mut [T]) -> T {
+    for i in 0..row.len() {
+        if is_constant(&row[i..]) {
+            return row[0..=i].iter().enumerate().fold(T::ZERO, |sum, (i, &n)| {
+                if i % 2 == 0 {
+                    sum + n
+                } else {
+                    sum - n
+                }
+            });
+        }
+
+        for j in (i + 1..row.len()).rev() {
+            row[j] = row[j] - row[j - 1];
+        }
+    }
+    panic!("cannot extrapolate an empty sequence")
+}
+
+/// Returns the value `n` steps after the last value of a sequence given by a
+/// polynomial, so that `extrapolate_n(row, 1)` is the same as
+/// `extrapolate(row)`.
+///
+/// `row` is overwritten with the differences. Panics if `row` is empty.
+pub fn extrapolate_n<T: Int>(row: &mut [T], n: usize) -> T {
+    let levels = last_differences(row);
+    let diffs = &mut row[..levels];
+
+    // each step adds the next level of differences to each level, starting
+    // from the constant one
+    for _ in 0..n {
+        for i in (0..levels - 1).rev() {
+            diffs[i] = diffs[i] + diffs[i + 1];
+        }
+    }
+
+    diffs[0]
+}
+
+/// Takes finite differences of `row` in place until they become constant.
+///
+/// Returns the number of levels of differences, including `row` itself. The
+/// first that many values of `row` are then the last value of each level.
+fn last_differences<T: Int>(row: &mut [T]) -> usize {
+    for i in 0..row.len() {
+        if is_constant(&row[i..]) {
+            return i + 1;
+        }
+
+        let last = *row.last().unwrap();
+        for j in (i + 1..row.len()).rev() {
+            row[j] = row[j] - row[j - 1];
+        }
+        row[i] = last;
+    }
+    panic!("cannot extrapolate an empty sequence")
+}
+
+fn is_constant<T: Int>(row: &[T]) -> bool {
+    if row.len() < 2 {
+        return true;
+    }
+
+    let first = row[0];
+    row[1..].iter().all(|&n| n == first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quadratic() {
+        // n^2 + 1
+        assert_eq!(extrapolate(&mut [1, 2, 5, 10]), 17);
+        assert_eq!(extrapolate_back(&mut [1, 2, 5, 10]), 2);
+        assert_eq!(extrapolate_n(&mut [1, 2, 5, 10], 0), 10);
+        assert_eq!(extrapolate_n(&mut [1, 2, 5, 10], 1), 17);
+        assert_eq!(extrapolate_n(&mut [1i64, 2, 5], 1000), 1002 * 1002 + 1);
+    }
+}
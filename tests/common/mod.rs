@@ -0,0 +1,189 @@
+// Not every test crate uses every helper.
+#![allow(dead_code)]
+
+use std::{collections::BTreeMap, error::Error, fs, path::Path};
+
+use aoc_runner::{ArcStr, Runner};
+use frx_aoc::*;
+
+pub const YEAR: u32 = 2023;
+
+/// A runner factory function generated by `aoc_runner_derive::aoc_lib!`.
+type BuildRunner = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// A solver registered with `#[aoc(dayN, partM)]` or
+/// `#[aoc(dayN, partM, name)]`.
+#[derive(Copy, Clone)]
+pub struct Solver {
+    pub day: u32,
+    pub part: u32,
+    pub name: Option<&'static str>,
+    build: BuildRunner,
+}
+
+impl Solver {
+    /// Runs the generator and solver on `input`, and returns the formatted
+    /// answer.
+    pub fn run(&self, input: &ArcStr) -> Result<String, Box<dyn Error>> {
+        let runner = (self.build)(input.clone())?;
+        Ok(runner.try_run()?.to_string())
+    }
+}
+
+impl std::fmt::Display for Solver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {} part {}", self.day, self.part)?;
+        if let Some(name) = self.name {
+            write!(f, " ({name})")?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! solver {
+    ($day:literal, $part:literal, $build:ident) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: None,
+            build: Factory::$build,
+        }
+    };
+    ($day:literal, $part:literal, $name:ident, $build:ident) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: Some(stringify!($name)),
+            build: Factory::$build,
+        }
+    };
+}
+
+/// Every registered solver, including alternative implementations.
+pub const SOLVERS: &[Solver] = &[
+    solver!(1, 1, day1_part1),
+    solver!(1, 2, day1_part2),
+    solver!(2, 1, day2_part1),
+    solver!(2, 2, day2_part2),
+    solver!(3, 1, day3_part1),
+    solver!(3, 2, day3_part2),
+    solver!(4, 1, day4_part1),
+    solver!(4, 2, day4_part2),
+    solver!(5, 1, day5_part1),
+    solver!(5, 2, day5_part2),
+    solver!(6, 1, direct_search, day6_part1_direct_search),
+    solver!(6, 1, binary_search, day6_part1_binary_search),
+    solver!(6, 1, solve_quadratic, day6_part1_solve_quadratic),
+    solver!(
+        6,
+        1,
+        solve_quadratic_exact,
+        day6_part1_solve_quadratic_exact
+    ),
+    solver!(6, 2, direct_search, day6_part2_direct_search),
+    solver!(6, 2, binary_search, day6_part2_binary_search),
+    solver!(6, 2, solve_quadratic, day6_part2_solve_quadratic),
+    solver!(
+        6,
+        2,
+        solve_quadratic_exact,
+        day6_part2_solve_quadratic_exact
+    ),
+    solver!(7, 1, day7_part1),
+    solver!(7, 2, day7_part2),
+    solver!(8, 1, day8_part1),
+    solver!(8, 2, day8_part2),
+    solver!(9, 1, day9_part1),
+    solver!(9, 2, day9_part2),
+    solver!(9, 2, reverse, day9_part2_reverse),
+    solver!(10, 1, day10_part1),
+    solver!(10, 2, scanline, day10_part2_scanline),
+    solver!(10, 2, shoelace, day10_part2_shoelace),
+    solver!(11, 1, day11_part1),
+    solver!(11, 2, day11_part2),
+    solver!(12, 1, day12_part1),
+    solver!(12, 2, day12_part2),
+    solver!(12, 2, parallel, day12_part2_parallel),
+    solver!(13, 1, day13_part1),
+    solver!(13, 2, day13_part2),
+    solver!(14, 1, day14_part1),
+    solver!(14, 2, day14_part2),
+    solver!(15, 1, day15_part1),
+    solver!(15, 2, day15_part2),
+    solver!(16, 1, day16_part1),
+    solver!(16, 2, day16_part2),
+    solver!(16, 2, naive, day16_part2_naive),
+    solver!(17, 1, day17_part1),
+    solver!(17, 2, day17_part2),
+    solver!(18, 1, day18_part1),
+    solver!(18, 2, day18_part2),
+    solver!(19, 1, day19_part1),
+    solver!(19, 2, day19_part2),
+    solver!(20, 1, day20_part1),
+    solver!(20, 2, day20_part2),
+    solver!(21, 1, day21_part1),
+    solver!(21, 2, day21_part2),
+    solver!(22, 1, day22_part1),
+    solver!(22, 2, day22_part2),
+    solver!(23, 1, day23_part1),
+    solver!(23, 2, day23_part2),
+    solver!(24, 1, day24_part1),
+    solver!(24, 2, day24_part2),
+];
+
+/// Returns all registered solvers for `day` and `part`.
+pub fn solvers(day: u32, part: u32) -> impl Iterator<Item = &'static Solver> {
+    SOLVERS
+        .iter()
+        .filter(move |solver| solver.day == day && solver.part == part)
+}
+
+/// Reads the puzzle input for `day`, if it is present.
+pub fn read_input(day: u32) -> Option<ArcStr> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("input")
+        .join(YEAR.to_string())
+        .join(format!("day{day}.txt"));
+
+    fs::read_to_string(path).ok().map(|s| ArcStr::from(&s))
+}
+
+/// Reads the committed answers file, if it is present, as a map from
+/// `(day, part)` to the expected answer.
+pub fn read_answers() -> Option<BTreeMap<(u32, u32), String>> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("answers")
+        .join(format!("{YEAR}.toml"));
+    let answers = fs::read_to_string(&path).ok()?;
+
+    let table: toml::Table = answers
+        .parse()
+        .unwrap_or_else(|e| panic!("invalid answers file {}: {e}", path.display()));
+
+    let mut map = BTreeMap::new();
+    for (day_key, parts) in table {
+        let day = day_key
+            .strip_prefix("day")
+            .and_then(|day| day.parse().ok())
+            .unwrap_or_else(|| panic!("invalid day in answers file: {day_key:?}"));
+        let parts = parts
+            .as_table()
+            .unwrap_or_else(|| panic!("expected a table for {day_key:?} in answers file"));
+
+        for (part_key, answer) in parts {
+            let part = part_key
+                .strip_prefix("part")
+                .and_then(|part| part.parse().ok())
+                .unwrap_or_else(|| panic!("invalid part in answers file: {part_key:?}"));
+            let answer = match answer {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Integer(n) => n.to_string(),
+                _ => panic!("invalid answer for {day_key}.{part_key}: {answer}"),
+            };
+
+            map.insert((day, part), answer);
+        }
+    }
+
+    Some(map)
+}
@@ -0,0 +1,248 @@
+//! Property tests checking that all alternative solvers registered for the
+//! same day and part agree with each other on randomly generated inputs.
+
+mod common;
+
+use aoc_runner::ArcStr;
+use itertools::Itertools;
+use proptest::{prelude::*, test_runner::TestCaseError};
+
+use common::{solvers, Solver};
+
+/// Runs every solver for `day` and `part` on `input`, and fails unless they
+/// all succeed with the same answer.
+fn check_variants_agree(day: u32, part: u32, input: &str) -> Result<(), TestCaseError> {
+    check_solvers_agree(solvers(day, part), input)
+}
+
+/// Runs each of `solvers` on `input`, and fails unless they all succeed with
+/// the same answer.
+fn check_solvers_agree<'a>(
+    solvers: impl Iterator<Item = &'a Solver>,
+    input: &str,
+) -> Result<(), TestCaseError> {
+    let input = ArcStr::from(input);
+
+    let results = solvers
+        .map(|solver| {
+            solver
+                .run(&input)
+                .map(|answer| (solver, answer))
+                .map_err(|e| TestCaseError::fail(format!("{solver}: {e}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    prop_assert!(
+        results.iter().map(|(_, answer)| answer).all_equal(),
+        "solvers disagree: {}",
+        results
+            .iter()
+            .map(|(solver, answer)| format!("{solver} = {answer}"))
+            .join(", ")
+    );
+    Ok(())
+}
+
+/// A day 6 race, as `(time, record distance)`, which may or may not be
+/// possible to win.
+fn race() -> impl Strategy<Value = (u64, u64)> {
+    (0..100u64).prop_flat_map(|time| (Just(time), 0..=time * time / 2))
+}
+
+/// A day 6 race long enough that `time * time` is beyond the precision of an
+/// f64, with a record that is within one of the distance for some hold time,
+/// so that rounding errors in the roots change the answer.
+fn long_race() -> impl Strategy<Value = (u64, u64)> {
+    (100_000_000..u32::MAX as u64)
+        .prop_flat_map(|time| (Just(time), 1..time / 2, -1..=1i64))
+        .prop_map(|(time, hold, offset)| {
+            (time, ((time - hold) * hold).saturating_add_signed(offset))
+        })
+}
+
+fn day6_input(races: &[(u64, u64)]) -> String {
+    let times = races.iter().map(|(time, _)| time).join(" ");
+    let dists = races.iter().map(|(_, dist)| dist).join(" ");
+    format!("Time: {times}\nDistance: {dists}")
+}
+
+/// A day 9 sequence of consecutive values of a random integer polynomial,
+/// long enough for the polynomial to be determined by it.
+fn polynomial_sequence() -> impl Strategy<Value = Vec<i32>> {
+    (prop::collection::vec(-5..=5i32, 1..=5), 0..5usize).prop_map(|(coeffs, extra)| {
+        (0..coeffs.len() as i32 + 1 + extra as i32)
+            .map(|x| coeffs.iter().rev().fold(0, |y, c| y * x + c))
+            .collect()
+    })
+}
+
+fn day9_input(sequences: &[Vec<i32>]) -> String {
+    sequences
+        .iter()
+        .map(|sequence| sequence.iter().join(" "))
+        .join("\n")
+}
+
+/// A day 10 pipe loop around a region that is convex in the vertical
+/// direction, given as a `(top, bottom)` pair of rows for each column of the
+/// region. Adjacent columns always overlap, so the loop never touches itself.
+fn pipe_loop() -> impl Strategy<Value = Vec<(usize, usize)>> {
+    prop::collection::vec((0..8usize, 0..8usize), 1..=8).prop_map(|columns| {
+        let mut region: Vec<(usize, usize)> = Vec::new();
+        for (a, b) in columns {
+            let (top, bottom) = match region.last() {
+                None => (a.min(b), a.max(b) + 1),
+                Some(&(prev_top, prev_bottom)) => {
+                    let top = a.min(prev_bottom - 1);
+                    (top, b.max(prev_top + 1).max(top + 1))
+                }
+            };
+            region.push((top, bottom));
+        }
+        region
+    })
+}
+
+/// Renders a day 10 maze from a loop generated by [`pipe_loop`], with the
+/// start tile at index `start` of the loop and every other tile filled from
+/// `junk`.
+fn day10_input(region: &[(usize, usize)], start: usize, junk: &[u8]) -> String {
+    // corners of the loop, clockwise from the top left, where each column of
+    // the region is two tiles wide
+    let mut corners = Vec::new();
+    for (c, &(top, _)) in region.iter().enumerate() {
+        if c > 0 {
+            corners.push((top, 2 * c - 1));
+        }
+        corners.extend([(top, 2 * c), (top, 2 * c + 1)]);
+    }
+    for (c, &(_, bottom)) in region.iter().enumerate().rev() {
+        if c + 1 < region.len() {
+            corners.push((region[c + 1].1, 2 * c + 1));
+        }
+        corners.extend([(bottom, 2 * c + 1), (bottom, 2 * c)]);
+    }
+    corners.push((region[0].0, 0));
+
+    let mut path = vec![corners[0]];
+    for &(row, col) in &corners[1..] {
+        while *path.last().unwrap() != (row, col) {
+            let (r, c) = *path.last().unwrap();
+            // corners only ever differ in one coordinate
+            path.push(if r != row {
+                (if r < row { r + 1 } else { r - 1 }, c)
+            } else {
+                (r, if c < col { c + 1 } else { c - 1 })
+            });
+        }
+    }
+    path.pop();
+
+    let height = region.iter().map(|&(_, bottom)| bottom).max().unwrap() + 1;
+    let width = 2 * region.len();
+    let mut grid = vec![vec![b'.'; width]; height];
+    for (i, row) in grid.iter_mut().enumerate() {
+        for (j, tile) in row.iter_mut().enumerate() {
+            *tile = junk[(i * width + j) % junk.len()];
+        }
+    }
+
+    let n = path.len();
+    for i in 0..n {
+        let (prev, (row, col), next) = (path[(i + n - 1) % n], path[i], path[(i + 1) % n]);
+        let tile = match [prev, next]
+            .map(|(r, c)| (r as isize - row as isize, c as isize - col as isize))
+        {
+            [(-1, 0), (1, 0)] | [(1, 0), (-1, 0)] => b'|',
+            [(0, -1), (0, 1)] | [(0, 1), (0, -1)] => b'-',
+            [(-1, 0), (0, 1)] | [(0, 1), (-1, 0)] => b'L',
+            [(-1, 0), (0, -1)] | [(0, -1), (-1, 0)] => b'J',
+            [(1, 0), (0, -1)] | [(0, -1), (1, 0)] => b'7',
+            [(1, 0), (0, 1)] | [(0, 1), (1, 0)] => b'F',
+            deltas => unreachable!("not a pipe: {deltas:?}"),
+        };
+        grid[row][col] = tile;
+    }
+
+    // junk pipes next to the start tile would make its shape ambiguous
+    let (row, col) = path[start % n];
+    for (r, c) in [
+        (row.wrapping_sub(1), col),
+        (row + 1, col),
+        (row, col.wrapping_sub(1)),
+        (row, col + 1),
+    ] {
+        if r < height && c < width && !path.contains(&(r, c)) {
+            grid[r][c] = b'.';
+        }
+    }
+    grid[row][col] = b'S';
+
+    grid.iter()
+        .map(|row| String::from_utf8_lossy(row))
+        .join("\n")
+}
+
+/// A day 12 row of springs and its list of group sizes, which may not have
+/// any valid arrangements.
+fn spring_record() -> impl Strategy<Value = String> {
+    (
+        prop::collection::vec(prop::sample::select(vec!['.', '#', '?']), 1..12),
+        prop::collection::vec(1..4usize, 1..4),
+    )
+        .prop_map(|(springs, groups)| {
+            format!("{} {}", springs.iter().join(""), groups.iter().join(","))
+        })
+}
+
+/// A day 16 grid of random mirrors and splitters.
+fn mirror_grid() -> impl Strategy<Value = String> {
+    (1..10usize, 1..10usize).prop_flat_map(|(width, height)| {
+        let tile = prop::sample::select(vec!['.', '.', '.', '/', '\\', '|', '-']);
+        prop::collection::vec(prop::collection::vec(tile, width), height)
+            .prop_map(|rows| rows.iter().map(|row| row.iter().join("")).join("\n"))
+    })
+}
+
+proptest! {
+    #[test]
+    fn day6_part1(races in prop::collection::vec(race(), 1..=4)) {
+        check_variants_agree(6, 1, &day6_input(&races))?;
+    }
+
+    #[test]
+    fn day6_part2(races in prop::collection::vec(race(), 1..=3)) {
+        check_variants_agree(6, 2, &day6_input(&races))?;
+    }
+
+    #[test]
+    fn day6_long_race(race in long_race(), part in 1..=2u32) {
+        // the direct search takes time linear in the length of the race
+        let fast_solvers = solvers(6, part).filter(|solver| solver.name != Some("direct_search"));
+        check_solvers_agree(fast_solvers, &day6_input(&[race]))?;
+    }
+
+    #[test]
+    fn day9_part2(sequences in prop::collection::vec(polynomial_sequence(), 1..=5)) {
+        check_variants_agree(9, 2, &day9_input(&sequences))?;
+    }
+
+    #[test]
+    fn day10_part2(
+        region in pipe_loop(),
+        start in any::<usize>(),
+        junk in prop::collection::vec(prop::sample::select(b"|-LJ7F.".to_vec()), 1..=16),
+    ) {
+        check_variants_agree(10, 2, &day10_input(&region, start, &junk))?;
+    }
+
+    #[test]
+    fn day12_part2(records in prop::collection::vec(spring_record(), 1..=5)) {
+        check_variants_agree(12, 2, &records.join("\n"))?;
+    }
+
+    #[test]
+    fn day16_part2(grid in mirror_grid()) {
+        check_variants_agree(16, 2, &grid)?;
+    }
+}
@@ -0,0 +1,59 @@
+//! Runs every registered solver, including alternative implementations,
+//! against the puzzle inputs in `input/2023/`, and checks that all solvers
+//! for the same day and part agree with each other and with the answer
+//! recorded in `answers/2023.toml`.
+
+mod common;
+
+use itertools::Itertools;
+
+use common::{read_answers, read_input, SOLVERS};
+
+#[test]
+fn verify() {
+    let answers = read_answers().expect("answers file is missing");
+    let mut failures = Vec::new();
+
+    let groups = SOLVERS
+        .iter()
+        .into_group_map_by(|solver| (solver.day, solver.part));
+
+    for ((day, part), solvers) in groups.into_iter().sorted_by_key(|(key, _)| *key) {
+        let Some(input) = read_input(day) else {
+            continue;
+        };
+
+        let expected = answers.get(&(day, part));
+        if expected.is_none() {
+            failures.push(format!("day {day} part {part}: no recorded answer"));
+        }
+
+        let mut results = Vec::new();
+        for solver in solvers {
+            match solver.run(&input) {
+                Ok(answer) => {
+                    match expected {
+                        Some(expected) if *expected != answer => {
+                            failures.push(format!("{solver}: expected {expected}, got {answer}"));
+                        }
+                        _ => {}
+                    }
+                    results.push((solver, answer));
+                }
+                Err(e) => failures.push(format!("{solver}: {e}")),
+            }
+        }
+
+        if !results.iter().map(|(_, answer)| answer).all_equal() {
+            let results = results
+                .iter()
+                .map(|(solver, answer)| format!("{solver} = {answer}"))
+                .join(", ");
+            failures.push(format!(
+                "day {day} part {part}: solvers disagree: {results}"
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}